@@ -0,0 +1,623 @@
+use chrono::{DateTime, Utc};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use iss_ratatui::config::{Config, Providers, SatelliteConfig};
+use iss_ratatui::error::IssError;
+use iss_ratatui::export::{ExportFormat, Tracks};
+use iss_ratatui::groundtrack::predict_positions;
+use iss_ratatui::http::HttpClient;
+use iss_ratatui::iss::{get_crew, get_position, get_weather, Crossing, Iss};
+use iss_ratatui::oem::OemEphemeris;
+use iss_ratatui::orbit::Propagator;
+use iss_ratatui::passes::{
+    compass_point, predict_passes, Observer, Pass, PASS_MIN_ELEVATION, PASS_PREDICTION_HOURS,
+};
+use iss_ratatui::provider::{
+    OpenNotify, PositionProvider, PropagatorProvider, ProviderKind, WhereTheIss,
+};
+use iss_ratatui::recorder::{csv_field, read_samples, Recorder, Replay, Sample};
+use iss_ratatui::server::ApiServer;
+use iss_ratatui::tle::{fetch_tle, Sgp4, Tle};
+use iss_ratatui::ui::App;
+use iss_ratatui::weather::Weather;
+use iss_ratatui::worker::Worker;
+use serde::Serialize;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Track the International Space Station. Without a subcommand the interactive tracker starts.
+#[derive(Debug, Parser)]
+#[command(name = "rs_iss", version)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Configuration file to read instead of rs_iss/config.toml in the user's config directory
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+
+    /// Where positions come from: wheretheiss, open-notify, oem or tle
+    #[arg(long, global = true)]
+    pub provider: Option<ProviderKind>,
+
+    /// Read the TLE from a file instead of downloading it from CelesTrak
+    #[arg(long, global = true)]
+    pub tle: Option<PathBuf>,
+
+    /// Append every fetched position to this file (CSV if it ends in .csv, JSON Lines otherwise)
+    #[arg(long, global = true)]
+    pub record: Option<PathBuf>,
+
+    /// Drive the tracker from a recording made with --record instead of the network
+    #[arg(long, conflicts_with = "record")]
+    pub replay: Option<PathBuf>,
+
+    /// Playback speed of --replay, as a multiple of real time
+    #[arg(long, default_value_t = 1.0)]
+    pub replay_speed: f64,
+
+    /// Number of future orbits to predict, on the map and in exports (above 0, at most 16)
+    #[arg(long, global = true)]
+    pub orbits: Option<f64>,
+
+    /// NORAD ID of another satellite to track alongside the ISS; may be repeated
+    #[arg(long = "satellite", value_name = "NORAD_ID")]
+    pub satellites: Vec<u32>,
+
+    #[command(flatten)]
+    pub observer: ObserverArgs,
+
+    /// Output format of the subcommands
+    #[arg(long, global = true, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+}
+
+/// Where passes are predicted for.
+#[derive(Debug, Args)]
+pub struct ObserverArgs {
+    /// Observer latitude in degrees
+    #[arg(long, global = true, allow_negative_numbers = true)]
+    pub lat: Option<f64>,
+
+    /// Observer longitude in degrees
+    #[arg(long, global = true, allow_negative_numbers = true)]
+    pub lon: Option<f64>,
+
+    /// Observer height above the WGS84 ellipsoid in metres
+    #[arg(long, global = true, allow_negative_numbers = true)]
+    pub elevation: Option<f64>,
+
+    /// Name shown on the Passes screen
+    #[arg(long, global = true)]
+    pub observer_name: Option<String>,
+}
+
+impl Cli {
+    /// Reads the configuration file and overrides it with whatever was given on the command line.
+    pub fn load_config(&self) -> Result<Config, IssError> {
+        let mut config = Config::load(self.config.as_deref())?;
+        if let Some(provider) = self.provider {
+            config.providers.default = provider;
+        }
+        if let Some(orbits) = self.orbits {
+            config.ui.orbits = orbits;
+        }
+        let observer = &self.observer;
+        config.observer.lat = observer.lat.or(config.observer.lat);
+        config.observer.lon = observer.lon.or(config.observer.lon);
+        if let Some(elevation) = observer.elevation {
+            config.observer.elevation = elevation;
+        }
+        if let Some(name) = &observer.observer_name {
+            config.observer.name = name.clone();
+        }
+        for &norad_id in &self.satellites {
+            if !config.satellites.iter().any(|satellite| satellite.norad_id == norad_id) {
+                config.satellites.push(SatelliteConfig {
+                    norad_id,
+                    name: None,
+                    color: None,
+                });
+            }
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Print the current position of the ISS
+    Now,
+    /// Print the people currently in space
+    Crew,
+    /// Print the weather below the ISS, or at --lat/--lon
+    Weather,
+    /// Print upcoming passes over the observer at --lat/--lon
+    Passes {
+        /// How far ahead to look
+        #[arg(long, default_value_t = PASS_PREDICTION_HOURS)]
+        hours: i64,
+
+        /// Lowest culmination (degrees) worth reporting; rise and set are still at the horizon
+        #[arg(long, default_value_t = PASS_MIN_ELEVATION)]
+        min_elevation: f64,
+    },
+    /// Write the predicted ground track, and optionally a recorded one, to GeoJSON, KML or GPX
+    Export {
+        /// Output file; the format is taken from its extension (.geojson, .kml or .gpx)
+        #[arg(long, short)]
+        output: PathBuf,
+
+        /// Recording made with --record to export as the past track
+        #[arg(long)]
+        from: Option<PathBuf>,
+    },
+    /// Print the position every --interval seconds until interrupted
+    Track {
+        /// Seconds between samples
+        #[arg(long, default_value_t = 5)]
+        interval: u64,
+
+        /// Stop after this many samples
+        #[arg(long)]
+        count: Option<u64>,
+    },
+    /// Run the tracker without the terminal UI and serve its state as JSON over HTTP
+    Serve {
+        /// Address to listen on; use 0.0.0.0 to accept other machines
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+
+        /// Port to listen on
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+    },
+    /// Manage the configuration file
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Write a commented configuration with every default, to --config or the default location
+    Init {
+        /// Replace an existing file
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+/// One sample of the ISS position, as printed by `now` and `track`.
+#[derive(Debug, Serialize)]
+struct PositionReport {
+    #[serde(flatten)]
+    sample: Sample,
+    provider: &'static str,
+    /// Set on the sample where the ground track entered a new country, sea or ocean.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    crossing: Option<Crossing>,
+}
+
+impl PositionReport {
+    fn from_iss(iss: &Iss) -> Self {
+        PositionReport {
+            sample: Sample::from_iss(iss),
+            provider: iss.provider.name(),
+            crossing: iss.crossing().cloned(),
+        }
+    }
+
+    fn print_csv_header() {
+        println!("{},provider", Sample::CSV_HEADER);
+    }
+
+    fn print(&self, format: Format) -> Result<(), Box<dyn Error>> {
+        let sample = &self.sample;
+        match format {
+            Format::Text => println!(
+                "{}  LAT {:.4}  LON {:.4}  ALT {:.1} km  {}  [{}, {}]",
+                sample.time.format("%Y-%m-%d %H:%M:%S UTC"),
+                sample.lat,
+                sample.lon,
+                sample.alt,
+                sample.country,
+                sample.source,
+                self.provider
+            ),
+            Format::Json => println!("{}", serde_json::to_string(self)?),
+            Format::Csv => println!("{},{}", sample.csv_row(), csv_field(self.provider)),
+        }
+        // CSV rows keep their columns, so crossings only show in text and JSON.
+        if let (Format::Text, Some(crossing)) = (format, &self.crossing) {
+            println!("  {crossing}");
+        }
+        Ok(())
+    }
+}
+
+/// The weather and where it was reported for, as printed by `weather`.
+#[derive(Debug, Serialize)]
+struct WeatherReport {
+    lat: f64,
+    lon: f64,
+    description: String,
+    #[serde(flatten)]
+    weather: Weather,
+}
+
+#[derive(Debug, Serialize)]
+struct PassesReport<'a> {
+    observer: &'a Observer,
+    passes: &'a [Pass],
+}
+
+/// Runs a non-interactive subcommand, printing to stdout in `cli.format`.
+pub fn run(cli: &Cli, config: &Config, command: &Command) -> Result<(), Box<dyn Error>> {
+    let client = config.client()?;
+    match command {
+        Command::Now => {
+            let mut iss = tracker(cli, config, &client);
+            let mut recorder = recorder(cli)?;
+            iss.update_position()?;
+            if let Some(recorder) = &mut recorder {
+                recorder.record(&Sample::from_iss(&iss))?;
+            }
+            if cli.format == Format::Csv {
+                PositionReport::print_csv_header();
+            }
+            PositionReport::from_iss(&iss).print(cli.format)
+        }
+        Command::Track { interval, count } => {
+            let mut iss = tracker(cli, config, &client);
+            let mut recorder = recorder(cli)?;
+            if cli.format == Format::Csv {
+                PositionReport::print_csv_header();
+            }
+            let mut samples = 0;
+            while count.map_or(true, |count| samples < count) {
+                if samples > 0 {
+                    std::thread::sleep(std::time::Duration::from_secs(*interval));
+                }
+                // A failed sample is reported and skipped rather than ending the stream.
+                match iss.update_position() {
+                    Ok(()) => {
+                        if let Some(recorder) = &mut recorder {
+                            recorder.record(&Sample::from_iss(&iss))?;
+                        }
+                        PositionReport::from_iss(&iss).print(cli.format)?;
+                    }
+                    Err(err) => eprintln!("{}", err),
+                }
+                samples += 1;
+            }
+            Ok(())
+        }
+        Command::Crew => print_crew(&get_crew(client.as_ref(), &config.providers.crew_url)?, cli.format),
+        Command::Weather => {
+            let (lat, lon) = match (cli.observer.lat, cli.observer.lon) {
+                (Some(lat), Some(lon)) => (lat, lon),
+                _ => {
+                    let position = get_position(client.as_ref(), &config.providers.where_the_iss_url)?;
+                    (position.lat, position.lon)
+                }
+            };
+            let weather = get_weather(client.as_ref(), &config.providers.weather_url, lat, lon, &config.units)?;
+            let report = WeatherReport {
+                lat,
+                lon,
+                description: weather.description(),
+                weather,
+            };
+            print_weather(&report, cli.format)
+        }
+        Command::Export { output, from } => {
+            let format = ExportFormat::from_path(output)
+                .ok_or("the output file must end in .geojson, .kml or .gpx")?;
+            let past = match from {
+                Some(path) => read_samples(path)?.iter().map(Sample::position).collect(),
+                None => Vec::new(),
+            };
+            let predicted = match oem_propagator(&config.providers)
+                .or_else(|| tle_propagator(cli.tle.as_deref(), &config.providers, client.as_ref()))
+            {
+                Some(propagator) => predict_positions(propagator.as_ref(), Utc::now(), config.ui.orbits),
+                None => Vec::new(),
+            };
+
+            let tracks = Tracks { past, predicted };
+            if tracks.is_empty() {
+                return Err("nothing to export: no recording given and no ephemeris available".into());
+            }
+            tracks.write(output, format)?;
+            eprintln!("Wrote {}", output.display());
+            Ok(())
+        }
+        Command::Passes {
+            hours,
+            min_elevation,
+        } => {
+            let observer = config
+                .observer
+                .observer()
+                .ok_or("passes needs an observer location: --lat <degrees> --lon <degrees>")?;
+            let propagator = match tle_propagator(cli.tle.as_deref(), &config.providers, client.as_ref()) {
+                Some(propagator) => propagator,
+                None => oem_propagator(&config.providers).ok_or("no TLE or OEM available to predict passes")?,
+            };
+            let passes = predict_passes(
+                propagator.as_ref(),
+                &observer,
+                Utc::now(),
+                *hours,
+                *min_elevation,
+            );
+            print_passes(&observer, &passes, cli.format)
+        }
+        Command::Serve { host, port } => serve(cli, config, client, &format!("{host}:{port}")),
+        Command::Config {
+            command: ConfigCommand::Init { force },
+        } => {
+            let path = match &cli.config {
+                Some(path) => path.clone(),
+                None => Config::default_path().ok_or("no configuration directory found, pass --config <file>")?,
+            };
+            if path.exists() && !force {
+                return Err(format!("{} already exists, pass --force to replace it", path.display()).into());
+            }
+            Config::write_default(&path)?;
+            eprintln!("Wrote {}", path.display());
+            Ok(())
+        }
+    }
+}
+
+/// The tracker the TUI and `serve` run: the configured provider, falling back to SGP4 or the
+/// OEM, checked against the OEM; recording or replaying as asked on the command line; and
+/// fetching in the background, with the configured satellites requested.
+pub fn start(
+    cli: &Cli,
+    config: Config,
+    client: Arc<dyn HttpClient>,
+) -> Result<(App, Iss), Box<dyn Error>> {
+    // Elements read from --tle are at hand straight away. The worker downloads the rest, and
+    // until they arrive positions come from wheretheiss.at.
+    let sgp4 = cli.tle.as_deref().and_then(|path| tle_propagator(Some(path), &config.providers, client.as_ref()));
+    let provider = configured_provider(&config.providers, &client, &None, &sgp4)
+        .unwrap_or_else(|| where_the_iss(&config.providers, &client));
+
+    let mut app = App::new(config)?;
+    app.recorder = recorder(cli)?;
+    if let Some(path) = &cli.replay {
+        app.replay = Some(Replay::new(read_samples(path)?, cli.replay_speed)?);
+    }
+    let mut iss = Iss::with_provider(provider);
+    iss.propagator = sgp4;
+    iss.alt = app.config.ui.altitude;
+    // Positions come from the replay instead of the network when there is one.
+    let live_provider = app.replay.is_none().then(|| iss.provider.box_clone());
+    app.worker = Some(Worker::spawn(
+        live_provider,
+        iss.propagator.clone(),
+        app.config.intervals.schedule(),
+        app.config.endpoints(),
+        client,
+    )?);
+    if let Some(worker) = &app.worker {
+        if cli.tle.is_none() {
+            worker.fetch_iss_tle(app.config.providers.tle_url.clone());
+        }
+        worker.fetch_oem(app.config.providers.oem_url().to_string());
+    }
+    app.update_position(&mut iss);
+    let satellites: Vec<u32> = app.config.satellites.iter().map(|satellite| satellite.norad_id).collect();
+    for norad_id in satellites {
+        app.track(norad_id);
+    }
+    Ok((app, iss))
+}
+
+/// Runs the tracker headless, as the TUI would but without drawing, and serves it at `addr`.
+fn serve(cli: &Cli, config: &Config, client: Arc<dyn HttpClient>, addr: &str) -> Result<(), Box<dyn Error>> {
+    let (mut app, mut iss) = start(cli, config.clone(), client)?;
+
+    let server = ApiServer::bind(addr)?;
+    if let Some(addr) = server.local_addr() {
+        eprintln!("Serving on http://{addr}");
+    }
+    server.run(&mut app, &mut iss)?;
+    Ok(())
+}
+
+/// An [`Iss`] reading from the configured provider, without the TUI's fallbacks.
+fn tracker(cli: &Cli, config: &Config, client: &Arc<dyn HttpClient>) -> Iss {
+    let providers = &config.providers;
+    let (oem, sgp4) = match providers.default {
+        ProviderKind::Oem => (oem_propagator(providers), None),
+        ProviderKind::Tle => (None, tle_propagator(cli.tle.as_deref(), providers, client.as_ref())),
+        ProviderKind::WhereTheIss | ProviderKind::OpenNotify => (None, None),
+    };
+
+    let mut iss = Iss::with_provider(provider(providers, client, &oem, &sgp4));
+    iss.propagator = sgp4.or(oem);
+    iss
+}
+
+/// The recorder for `--record`, if one was asked for.
+pub fn recorder(cli: &Cli) -> Result<Option<Recorder>, IssError> {
+    cli.record.as_deref().map(Recorder::open).transpose()
+}
+
+/// Builds the configured provider (`--provider` or `providers.default`), falling back to
+/// wheretheiss.at when the local ephemeris it needs is unavailable.
+pub fn provider(
+    providers: &Providers,
+    client: &Arc<dyn HttpClient>,
+    oem: &Option<Arc<dyn Propagator>>,
+    sgp4: &Option<Arc<dyn Propagator>>,
+) -> Box<dyn PositionProvider> {
+    configured_provider(providers, client, oem, sgp4).unwrap_or_else(|| {
+        eprintln!("No local ephemeris available, falling back to wheretheiss.at");
+        where_the_iss(providers, client)
+    })
+}
+
+/// The configured provider, or `None` when it needs a local ephemeris that is unavailable.
+fn configured_provider(
+    providers: &Providers,
+    client: &Arc<dyn HttpClient>,
+    oem: &Option<Arc<dyn Propagator>>,
+    sgp4: &Option<Arc<dyn Propagator>>,
+) -> Option<Box<dyn PositionProvider>> {
+    match (providers.default, oem, sgp4) {
+        (ProviderKind::WhereTheIss, _, _) => Some(where_the_iss(providers, client)),
+        (ProviderKind::OpenNotify, _, _) => Some(Box::new(OpenNotify {
+            url: providers.open_notify_url.clone(),
+            client: client.clone(),
+        })),
+        (ProviderKind::Oem, Some(oem), _) => Some(Box::new(PropagatorProvider::new(oem.clone()))),
+        (ProviderKind::Tle, _, Some(sgp4)) => Some(Box::new(PropagatorProvider::new(sgp4.clone()))),
+        (ProviderKind::Oem | ProviderKind::Tle, _, _) => None,
+    }
+}
+
+fn where_the_iss(providers: &Providers, client: &Arc<dyn HttpClient>) -> Box<dyn PositionProvider> {
+    Box::new(WhereTheIss {
+        url: providers.where_the_iss_url.clone(),
+        client: client.clone(),
+    })
+}
+
+/// SGP4 from the TLE at `path`, or from `providers.tle_url` when no path is given.
+pub fn tle_propagator(
+    path: Option<&Path>,
+    providers: &Providers,
+    client: &dyn HttpClient,
+) -> Option<Arc<dyn Propagator>> {
+    let tle = match path {
+        Some(path) => Tle::from_file(path),
+        None => fetch_tle(client, &providers.tle_url),
+    };
+    match tle.and_then(Sgp4::new) {
+        Ok(sgp4) => Some(Arc::new(sgp4)),
+        Err(err) => {
+            eprintln!("No TLE available for offline propagation: {}", err);
+            None
+        }
+    }
+}
+
+/// Downloads and parses the OEM at `providers.oem_url`.
+fn oem_propagator(providers: &Providers) -> Option<Arc<dyn Propagator>> {
+    let content = OrbitalEphemerisMessage::download_file(providers.oem_url()).ok()?;
+    let ephemeris = OemEphemeris::parse(&content);
+    if ephemeris.is_empty() {
+        None
+    } else {
+        Some(Arc::new(ephemeris))
+    }
+}
+
+fn print_crew(crew: &[String], format: Format) -> Result<(), Box<dyn Error>> {
+    match format {
+        Format::Text => crew.iter().for_each(|name| println!("{}", name)),
+        Format::Json => println!("{}", serde_json::to_string(crew)?),
+        Format::Csv => {
+            println!("name");
+            crew.iter().for_each(|name| println!("{}", csv_field(name)));
+        }
+    }
+    Ok(())
+}
+
+fn print_weather(report: &WeatherReport, format: Format) -> Result<(), Box<dyn Error>> {
+    let weather = &report.weather;
+    match format {
+        Format::Text => println!(
+            "{} {}, {}, wind {} {} from {}, cloud cover {}%, precipitation {} {} (at {:.4}, {:.4})",
+            weather.temperature,
+            weather.temperature_unit,
+            report.description,
+            weather.wind_speed,
+            weather.wind_speed_unit,
+            compass_point(weather.wind_direction),
+            weather.cloud_cover,
+            weather.precipitation,
+            weather.precipitation_unit,
+            report.lat,
+            report.lon
+        ),
+        Format::Json => println!("{}", serde_json::to_string(report)?),
+        Format::Csv => {
+            println!("lat,lon,description,temperature,temperature_unit,weather_code,wind_speed,wind_speed_unit,wind_direction,cloud_cover,is_day,precipitation,precipitation_unit");
+            println!(
+                "{},{},{},{},{},{},{},{},{},{},{},{},{}",
+                report.lat,
+                report.lon,
+                csv_field(&report.description),
+                weather.temperature,
+                csv_field(&weather.temperature_unit),
+                weather.weather_code,
+                weather.wind_speed,
+                csv_field(&weather.wind_speed_unit),
+                weather.wind_direction,
+                weather.cloud_cover,
+                weather.is_day,
+                weather.precipitation,
+                csv_field(&weather.precipitation_unit)
+            );
+        }
+    }
+    Ok(())
+}
+
+fn print_passes(observer: &Observer, passes: &[Pass], format: Format) -> Result<(), Box<dyn Error>> {
+    match format {
+        Format::Text => {
+            let time = |time: DateTime<Utc>| time.format("%Y-%m-%d %H:%M:%S").to_string();
+            let azimuth = |azimuth: f64| format!("{:>3.0}° {:<2}", azimuth, compass_point(azimuth));
+            println!("Passes over {} ({:.4}, {:.4}), times in UTC", observer.name, observer.lat, observer.lon);
+            for pass in passes {
+                println!(
+                    "rise {} {}  max {:>2.0}° at {} {}  set {} {}{}",
+                    time(pass.rise),
+                    azimuth(pass.rise_azimuth),
+                    pass.max_elevation,
+                    time(pass.culmination),
+                    azimuth(pass.culmination_azimuth),
+                    time(pass.set),
+                    azimuth(pass.set_azimuth),
+                    if pass.visible { "  visible" } else { "" }
+                );
+            }
+        }
+        Format::Json => println!("{}", serde_json::to_string(&PassesReport { observer, passes })?),
+        Format::Csv => {
+            println!("rise,rise_azimuth,culmination,culmination_azimuth,max_elevation,set,set_azimuth,visible");
+            for pass in passes {
+                println!(
+                    "{},{:.1},{},{:.1},{:.1},{},{:.1},{}",
+                    pass.rise.to_rfc3339(),
+                    pass.rise_azimuth,
+                    pass.culmination.to_rfc3339(),
+                    pass.culmination_azimuth,
+                    pass.max_elevation,
+                    pass.set.to_rfc3339(),
+                    pass.set_azimuth,
+                    pass.visible
+                );
+            }
+        }
+    }
+    Ok(())
+}
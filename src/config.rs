@@ -0,0 +1,491 @@
+use crate::error::IssError;
+use crate::http::{HttpClient, ReqwestClient, DEFAULT_TIMEOUT};
+use crate::iss::OPEN_NOTIFY_CREW_URL;
+use crate::passes::Observer;
+use crate::provider::{ProviderKind, DEFAULT_ALTITUDE_KM, OPEN_NOTIFY_URL, WHERE_THE_ISS_URL};
+use crate::satellite::ISS_NORAD_ID;
+use crate::tle::{celestrak_tle_url, CELESTRAK_GP_URL, CELESTRAK_TLE_URL};
+use crate::weather::{WeatherUnits, OPEN_METEO_URL};
+use crate::worker::{Endpoints, Schedule};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Most future orbits `ui.orbits` may ask for: about a day of the ISS's.
+pub const MAX_ORBITS: f64 = 16.0;
+
+/// What `rs_iss config init` writes: every setting at its default, with comments.
+pub const DEFAULT_CONFIG: &str = r##"# rs_iss configuration. Every value below is the default; delete any you do not need.
+# Command line options take precedence over this file.
+
+[intervals]
+# How often the position is refreshed, in milliseconds.
+refresh_ms = 5500
+# How long the tracker waits for a key press before redrawing, in milliseconds.
+poll_ms = 250
+# How often the crew list is refreshed, in minutes.
+crew_minutes = 60
+# How often the weather below the ISS is refreshed, in minutes.
+weather_minutes = 10
+
+[providers]
+# Where positions come from: wheretheiss, open-notify, oem or tle.
+default = "wheretheiss"
+# Seconds to wait for an API to answer before giving up.
+timeout_secs = 10
+where_the_iss_url = "https://api.wheretheiss.at/v1/satellites/25544"
+open_notify_url = "http://api.open-notify.org/iss-now.json"
+crew_url = "http://api.open-notify.org/astros.json"
+weather_url = "https://api.open-meteo.com/v1/forecast"
+tle_url = "https://celestrak.org/NORAD/elements/gp.php?CATNR=25544&FORMAT=TLE"
+# Where the elements of the other satellites below are downloaded from.
+celestrak_url = "https://celestrak.org/NORAD/elements/gp.php"
+# NASA's published ISS ephemeris is used unless another OEM file is given.
+# oem_url = "https://..."
+
+[observer]
+# Where passes are predicted for. Passes are only shown once both lat and lon are set.
+name = "Observer"
+# lat = 52.2053
+# lon = 0.1218
+# Height above the WGS84 ellipsoid in metres.
+elevation = 0.0
+
+[units]
+# celsius or fahrenheit
+temperature = "celsius"
+# kmh, ms, mph or kn
+wind_speed = "kmh"
+# mm or inch
+precipitation = "mm"
+
+[ui]
+# Screen shown at start: tracker, map, passes, crew, satellites or events.
+screen = "tracker"
+# Degrees of latitude and longitude shown either side of the ISS on the map.
+zoom = 50.0
+# Number of future orbits drawn on the map and exported, above 0 and at most 16.
+orbits = 2.0
+# Altitude in km shown until the first position arrives.
+altitude = 417.5
+# Elevation in degrees the ISS must be above to count as reachable. The map draws this
+# footprint inside the horizon footprint, and lists the countries within it.
+footprint_min_elevation = 10.0
+
+[theme]
+# Color names (e.g. "light blue"), 256-color indices or "#rrggbb".
+title = "cyan"
+map = "yellow"
+track = "light blue"
+past_track = "dark gray"
+iss = "red"
+# The day/night line and the point where the Sun is overhead.
+terminator = "gray"
+sun = "light yellow"
+footprint = "green"
+
+[keys]
+next_screen = "l"
+quit = "q"
+update = "u"
+zoom_in = "]"
+zoom_out = "["
+export = "e"
+# Replay speed, with --replay.
+faster = ">"
+slower = "<"
+# On the Satellites screen: prompt for a NORAD ID to track.
+add_satellite = "a"
+
+# Other satellites to track alongside the ISS, propagated from their CelesTrak elements.
+# name defaults to the name CelesTrak gives, and color to one picked in turn.
+# [[satellites]]
+# norad_id = 48274
+# name = "Tiangong"
+# color = "magenta"
+#
+# [[satellites]]
+# norad_id = 20580
+# name = "Hubble"
+"##;
+
+/// Settings read from `config.toml`. Anything missing from the file keeps its default.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub intervals: Intervals,
+    pub providers: Providers,
+    pub observer: ObserverConfig,
+    pub units: WeatherUnits,
+    pub ui: Ui,
+    pub theme: Theme,
+    pub keys: Keys,
+    pub satellites: Vec<SatelliteConfig>,
+}
+
+impl Config {
+    /// `rs_iss/config.toml` in the platform's config directory, e.g. `$XDG_CONFIG_HOME` on Linux.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("rs_iss").join("config.toml"))
+    }
+
+    /// Reads `path`, or the default path when none is given. A missing default file is not
+    /// an error: everything is left at its default.
+    pub fn load(path: Option<&Path>) -> Result<Config, IssError> {
+        match path {
+            Some(path) => Config::read(path),
+            None => match Config::default_path() {
+                Some(path) if path.exists() => Config::read(&path),
+                _ => Ok(Config::default()),
+            },
+        }
+    }
+
+    pub fn read(path: &Path) -> Result<Config, IssError> {
+        let text = std::fs::read_to_string(path)?;
+        let in_file = |msg: String| IssError::InvalidConfig(format!("{}: {msg}", path.display()));
+        let config: Config = toml::from_str(&text).map_err(|err| in_file(err.to_string()))?;
+        match config.validate() {
+            Err(IssError::InvalidConfig(msg)) => Err(in_file(msg)),
+            result => result.map(|()| config),
+        }
+    }
+
+    /// Rejects values the tracker cannot run with. Deserializing only checks the types, so this
+    /// is run on the file and again once the command line has overridden it.
+    pub fn validate(&self) -> Result<(), IssError> {
+        self.intervals.validate()?;
+        self.ui.validate()?;
+        self.keys.validate()
+    }
+
+    /// Writes [`DEFAULT_CONFIG`] to `path`, creating its directory if needed.
+    pub fn write_default(path: &Path) -> Result<(), IssError> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, DEFAULT_CONFIG)?;
+        Ok(())
+    }
+
+    /// The HTTP client every API is fetched with.
+    pub fn client(&self) -> Result<Arc<dyn HttpClient>, IssError> {
+        Ok(Arc::new(ReqwestClient::new(Duration::from_secs(self.providers.timeout_secs))?))
+    }
+
+    pub fn endpoints(&self) -> Endpoints {
+        Endpoints {
+            crew: self.providers.crew_url.clone(),
+            weather: self.providers.weather_url.clone(),
+            units: self.units,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Intervals {
+    pub refresh_ms: u64,
+    pub poll_ms: u64,
+    pub crew_minutes: u64,
+    pub weather_minutes: u64,
+}
+
+impl Default for Intervals {
+    fn default() -> Self {
+        Intervals {
+            refresh_ms: 5500,
+            poll_ms: 250,
+            crew_minutes: 60,
+            weather_minutes: 10,
+        }
+    }
+}
+
+impl Intervals {
+    pub fn refresh(&self) -> Duration {
+        Duration::from_millis(self.refresh_ms)
+    }
+
+    pub fn poll(&self) -> Duration {
+        Duration::from_millis(self.poll_ms)
+    }
+
+    pub fn schedule(&self) -> Schedule {
+        Schedule {
+            position: self.refresh(),
+            crew: Duration::from_secs(self.crew_minutes.saturating_mul(60)),
+            weather: Duration::from_secs(self.weather_minutes.saturating_mul(60)),
+        }
+    }
+
+    /// Every interval must be above zero, since timers cannot tick at zero, and short enough
+    /// that the next tick can still be scheduled.
+    fn validate(&self) -> Result<(), IssError> {
+        let minutes = |minutes: u64| minutes.checked_mul(60).map(Duration::from_secs);
+        for (name, value, period) in [
+            ("refresh_ms", self.refresh_ms, Some(self.refresh())),
+            ("poll_ms", self.poll_ms, Some(self.poll())),
+            ("crew_minutes", self.crew_minutes, minutes(self.crew_minutes)),
+            ("weather_minutes", self.weather_minutes, minutes(self.weather_minutes)),
+        ] {
+            if value == 0 {
+                return Err(IssError::InvalidConfig(format!("intervals.{name} must be greater than 0")));
+            }
+            if period.and_then(|period| Instant::now().checked_add(period)).is_none() {
+                return Err(IssError::InvalidConfig(format!("intervals.{name} = {value} is too long")));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Providers {
+    pub default: ProviderKind,
+    pub timeout_secs: u64,
+    pub where_the_iss_url: String,
+    pub open_notify_url: String,
+    pub crew_url: String,
+    pub weather_url: String,
+    pub tle_url: String,
+    pub celestrak_url: String,
+    /// NASA's published ephemeris when not set.
+    pub oem_url: Option<String>,
+}
+
+impl Default for Providers {
+    fn default() -> Self {
+        Providers {
+            default: ProviderKind::WhereTheIss,
+            timeout_secs: DEFAULT_TIMEOUT.as_secs(),
+            where_the_iss_url: WHERE_THE_ISS_URL.to_string(),
+            open_notify_url: OPEN_NOTIFY_URL.to_string(),
+            crew_url: OPEN_NOTIFY_CREW_URL.to_string(),
+            weather_url: OPEN_METEO_URL.to_string(),
+            tle_url: CELESTRAK_TLE_URL.to_string(),
+            celestrak_url: CELESTRAK_GP_URL.to_string(),
+            oem_url: None,
+        }
+    }
+}
+
+impl Providers {
+    pub fn oem_url(&self) -> &str {
+        self.oem_url.as_deref().unwrap_or(OrbitalEphemerisMessage::ISS_OEM_URL)
+    }
+
+    /// Where the TLE of `norad_id` is downloaded from.
+    pub fn tle_url_for(&self, norad_id: u32) -> String {
+        match norad_id {
+            ISS_NORAD_ID => self.tle_url.clone(),
+            _ => celestrak_tle_url(&self.celestrak_url, norad_id),
+        }
+    }
+}
+
+/// The default observer; `--lat`, `--lon`, `--elevation` and `--observer-name` override it.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ObserverConfig {
+    pub name: String,
+    pub lat: Option<f64>,
+    pub lon: Option<f64>,
+    pub elevation: f64,
+}
+
+impl Default for ObserverConfig {
+    fn default() -> Self {
+        ObserverConfig {
+            name: String::from("Observer"),
+            lat: None,
+            lon: None,
+            elevation: 0.0,
+        }
+    }
+}
+
+impl ObserverConfig {
+    pub fn observer(&self) -> Option<Observer> {
+        Some(Observer {
+            name: self.name.clone(),
+            lat: self.lat?,
+            lon: self.lon?,
+            elevation: self.elevation,
+        })
+    }
+}
+
+/// Screens of the interactive tracker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Screen {
+    #[default]
+    Tracker,
+    Map,
+    Passes,
+    Crew,
+    Satellites,
+    Events,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Ui {
+    pub screen: Screen,
+    pub zoom: f64,
+    pub orbits: f64,
+    /// Shown until the first position arrives (km).
+    pub altitude: f64,
+    /// Degrees above the horizon for the reachable footprint.
+    pub footprint_min_elevation: f64,
+}
+
+impl Default for Ui {
+    fn default() -> Self {
+        Ui {
+            screen: Screen::Tracker,
+            zoom: 50.0,
+            orbits: 2.0,
+            altitude: DEFAULT_ALTITUDE_KM,
+            footprint_min_elevation: 10.0,
+        }
+    }
+}
+
+impl Ui {
+    /// Orbits are predicted a minute at a time, so there must be some, and not so many that
+    /// predicting them stalls the tracker.
+    fn validate(&self) -> Result<(), IssError> {
+        if self.orbits.is_nan() || self.orbits <= 0.0 {
+            return Err(IssError::InvalidConfig(format!("ui.orbits must be greater than 0, not {}", self.orbits)));
+        }
+        if self.orbits > MAX_ORBITS {
+            return Err(IssError::InvalidConfig(format!("ui.orbits = {} is more than {MAX_ORBITS}", self.orbits)));
+        }
+        Ok(())
+    }
+}
+
+/// Colors of the interactive tracker, as names the TUI parses.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    pub title: String,
+    pub map: String,
+    pub track: String,
+    pub past_track: String,
+    pub iss: String,
+    pub terminator: String,
+    pub sun: String,
+    pub footprint: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            title: String::from("cyan"),
+            map: String::from("yellow"),
+            track: String::from("light blue"),
+            past_track: String::from("dark gray"),
+            iss: String::from("red"),
+            terminator: String::from("gray"),
+            sun: String::from("light yellow"),
+            footprint: String::from("green"),
+        }
+    }
+}
+
+/// Something a key of the interactive tracker does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    NextScreen,
+    Quit,
+    Update,
+    ZoomIn,
+    ZoomOut,
+    Export,
+    Faster,
+    Slower,
+    AddSatellite,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Keys {
+    pub next_screen: char,
+    pub quit: char,
+    pub update: char,
+    pub zoom_in: char,
+    pub zoom_out: char,
+    pub export: char,
+    pub faster: char,
+    pub slower: char,
+    pub add_satellite: char,
+}
+
+impl Default for Keys {
+    fn default() -> Self {
+        Keys {
+            next_screen: 'l',
+            quit: 'q',
+            update: 'u',
+            zoom_in: ']',
+            zoom_out: '[',
+            export: 'e',
+            faster: '>',
+            slower: '<',
+            add_satellite: 'a',
+        }
+    }
+}
+
+impl Keys {
+    /// The action bound to `key`, if any.
+    pub fn action(&self, key: char) -> Option<Action> {
+        self.bindings()
+            .into_iter()
+            .find(|&(_, bound, _)| bound == key)
+            .map(|(_, _, action)| action)
+    }
+
+    /// Every binding, with the setting it comes from.
+    fn bindings(&self) -> [(&'static str, char, Action); 9] {
+        [
+            ("next_screen", self.next_screen, Action::NextScreen),
+            ("quit", self.quit, Action::Quit),
+            ("update", self.update, Action::Update),
+            ("zoom_in", self.zoom_in, Action::ZoomIn),
+            ("zoom_out", self.zoom_out, Action::ZoomOut),
+            ("export", self.export, Action::Export),
+            ("faster", self.faster, Action::Faster),
+            ("slower", self.slower, Action::Slower),
+            ("add_satellite", self.add_satellite, Action::AddSatellite),
+        ]
+    }
+
+    /// A key bound twice would only ever do the first of its actions.
+    fn validate(&self) -> Result<(), IssError> {
+        let bindings = self.bindings();
+        for (index, (name, key, _)) in bindings.iter().enumerate() {
+            if let Some((other, _, _)) = bindings[..index].iter().find(|(_, bound, _)| bound == key) {
+                return Err(IssError::InvalidConfig(format!(
+                    "keys.{other} and keys.{name} are both bound to '{key}'"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A satellite tracked alongside the ISS, from `[[satellites]]`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SatelliteConfig {
+    pub norad_id: u32,
+    pub name: Option<String>,
+    /// A color name as in `[theme]`.
+    pub color: Option<String>,
+}
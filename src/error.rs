@@ -0,0 +1,72 @@
+use std::fmt;
+
+/// Everything that can go wrong while fetching ISS data.
+#[derive(Debug)]
+pub enum IssError {
+    /// The request could not be sent or the response body could not be read.
+    Network(reqwest::Error),
+    /// The server answered with a non-success status code.
+    HttpStatus { url: String, status: u16 },
+    /// The response body was not valid JSON.
+    Json(serde_json::Error),
+    /// The JSON was valid but a field was missing or had the wrong type.
+    MissingField(String),
+    /// No place could be found for the coordinates.
+    Geocode(String),
+    /// A local propagator could not produce a position.
+    Propagation(String),
+    /// A two-line element set could not be parsed.
+    InvalidTle(String),
+    /// A local file could not be read or written.
+    Io(std::io::Error),
+    /// A line of a position recording could not be parsed.
+    InvalidRecord(String),
+    /// The configuration file could not be parsed.
+    InvalidConfig(String),
+}
+
+impl fmt::Display for IssError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IssError::Network(err) => write!(f, "network error: {err}"),
+            IssError::HttpStatus { url, status } => write!(f, "HTTP {status} from {url}"),
+            IssError::Json(err) => write!(f, "invalid JSON: {err}"),
+            IssError::MissingField(field) => write!(f, "missing or invalid field '{field}'"),
+            IssError::Geocode(msg) => write!(f, "geocoding failed: {msg}"),
+            IssError::Propagation(msg) => write!(f, "propagation failed: {msg}"),
+            IssError::InvalidTle(msg) => write!(f, "invalid TLE: {msg}"),
+            IssError::Io(err) => write!(f, "I/O error: {err}"),
+            IssError::InvalidRecord(msg) => write!(f, "invalid recording: {msg}"),
+            IssError::InvalidConfig(msg) => write!(f, "invalid configuration: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for IssError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            IssError::Network(err) => Some(err),
+            IssError::Json(err) => Some(err),
+            IssError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for IssError {
+    fn from(err: reqwest::Error) -> Self {
+        IssError::Network(err)
+    }
+}
+
+impl From<std::io::Error> for IssError {
+    fn from(err: std::io::Error) -> Self {
+        IssError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for IssError {
+    fn from(err: serde_json::Error) -> Self {
+        IssError::Json(err)
+    }
+}
@@ -0,0 +1,168 @@
+use crate::error::IssError;
+use crate::groundtrack::split_at_antimeridian;
+use crate::provider::Position;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{json, Value};
+use std::fmt::Write as _;
+use std::path::Path;
+use std::str::FromStr;
+
+/// File formats the ground tracks can be exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    GeoJson,
+    Kml,
+    Gpx,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] = [ExportFormat::GeoJson, ExportFormat::Kml, ExportFormat::Gpx];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::GeoJson => "geojson",
+            ExportFormat::Kml => "kml",
+            ExportFormat::Gpx => "gpx",
+        }
+    }
+
+    /// The format matching the extension of `path`, if any.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()?.to_str()?.parse().ok()
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "geojson" | "json" => Ok(ExportFormat::GeoJson),
+            "kml" => Ok(ExportFormat::Kml),
+            "gpx" => Ok(ExportFormat::Gpx),
+            other => Err(format!("unknown export format '{other}', expected one of: geojson, kml, gpx")),
+        }
+    }
+}
+
+/// The flown and predicted tracks to export.
+#[derive(Debug, Clone, Default)]
+pub struct Tracks {
+    pub past: Vec<Position>,
+    pub predicted: Vec<Position>,
+}
+
+impl Tracks {
+    /// Both tracks as `(name, positions)`, skipping empty ones.
+    fn named(&self) -> impl Iterator<Item = (&'static str, &[Position])> {
+        [("Past track", self.past.as_slice()), ("Predicted track", self.predicted.as_slice())]
+            .into_iter()
+            .filter(|(_, positions)| !positions.is_empty())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.past.is_empty() && self.predicted.is_empty()
+    }
+
+    pub fn render(&self, format: ExportFormat) -> String {
+        match format {
+            ExportFormat::GeoJson => self.to_geojson(),
+            ExportFormat::Kml => self.to_kml(),
+            ExportFormat::Gpx => self.to_gpx(),
+        }
+    }
+
+    pub fn write(&self, path: &Path, format: ExportFormat) -> Result<(), IssError> {
+        std::fs::write(path, self.render(format))?;
+        Ok(())
+    }
+
+    /// A FeatureCollection with one LineString per piece of track between antimeridian
+    /// crossings, each timed by the first and last positions in that piece.
+    pub fn to_geojson(&self) -> String {
+        let mut features = Vec::new();
+        for (name, positions) in self.named() {
+            let points: Vec<(f64, f64)> =
+                positions.iter().map(|position| (position.lon, position.lat)).collect();
+            // Split where `split_at_antimeridian` splits, so the runs pair up with its segments.
+            let runs = positions.chunk_by(|before, after| (after.lon - before.lon).abs() <= 180.0);
+            for (segment, run) in split_at_antimeridian(&points).into_iter().zip(runs) {
+                let coordinates: Vec<Value> =
+                    segment.iter().map(|&(lon, lat)| json!([lon, lat])).collect();
+                features.push(json!({
+                    "type": "Feature",
+                    "properties": {
+                        "name": name,
+                        "start": timestamp(&run[0]),
+                        "end": timestamp(&run[run.len() - 1]),
+                    },
+                    "geometry": { "type": "LineString", "coordinates": coordinates },
+                }));
+            }
+        }
+
+        json!({ "type": "FeatureCollection", "features": features }).to_string()
+    }
+
+    /// One `gx:Track` per track, so Google Earth's time slider can animate the ISS along it.
+    pub fn to_kml(&self) -> String {
+        let mut kml = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<kml xmlns=\"http://www.opengis.net/kml/2.2\" xmlns:gx=\"http://www.google.com/kml/ext/2.2\">\n",
+            "<Document>\n<name>ISS ground track</name>\n",
+        ));
+        for (name, positions) in self.named() {
+            let _ = writeln!(kml, "<Placemark>\n<name>{name}</name>\n<gx:Track>");
+            kml.push_str("<altitudeMode>absolute</altitudeMode>\n");
+            for position in positions {
+                let _ = writeln!(kml, "<when>{}</when>", timestamp(position));
+            }
+            for position in positions {
+                let _ = writeln!(
+                    kml,
+                    "<gx:coord>{} {} {:.0}</gx:coord>",
+                    position.lon,
+                    position.lat,
+                    position.alt * 1000.0
+                );
+            }
+            kml.push_str("</gx:Track>\n</Placemark>\n");
+        }
+        kml.push_str("</Document>\n</kml>\n");
+        kml
+    }
+
+    /// One `trk` per track, with elevations in metres. A new `trkseg` starts wherever the
+    /// track wraps around the antimeridian.
+    pub fn to_gpx(&self) -> String {
+        let mut gpx = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<gpx version=\"1.1\" creator=\"rs_iss\" xmlns=\"http://www.topografix.com/GPX/1/1\">\n",
+        ));
+        for (name, positions) in self.named() {
+            let _ = writeln!(gpx, "<trk>\n<name>{name}</name>\n<trkseg>");
+            for (index, position) in positions.iter().enumerate() {
+                if index > 0 && (position.lon - positions[index - 1].lon).abs() > 180.0 {
+                    gpx.push_str("</trkseg>\n<trkseg>\n");
+                }
+                let _ = writeln!(
+                    gpx,
+                    "<trkpt lat=\"{}\" lon=\"{}\"><ele>{:.0}</ele><time>{}</time></trkpt>",
+                    position.lat,
+                    position.lon,
+                    position.alt * 1000.0,
+                    timestamp(position)
+                );
+            }
+            gpx.push_str("</trkseg>\n</trk>\n");
+        }
+        gpx.push_str("</gpx>\n");
+        gpx
+    }
+}
+
+fn timestamp(position: &Position) -> String {
+    DateTime::<Utc>::from_timestamp(position.timestamp as i64, 0)
+        .unwrap_or_default()
+        .to_rfc3339_opts(SecondsFormat::Secs, true)
+}
@@ -0,0 +1,117 @@
+use crate::groundtrack::split_at_antimeridian;
+use crate::orbit::EARTH_RADIUS_KM;
+
+/// Bearing step between points of a footprint's outline (degrees).
+const OUTLINE_STEP_DEGREES: f64 = 3.0;
+/// Spacing of the points sampled inside a footprint for the country list (degrees of arc).
+const SAMPLE_SPACING_DEGREES: f64 = 1.5;
+
+/// The area on the ground from which the ISS is at least `min_elevation` above the horizon,
+/// on a spherical Earth.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Footprint {
+    /// Sub-satellite latitude and longitude (degrees).
+    pub lat: f64,
+    pub lon: f64,
+    pub min_elevation: f64,
+    /// Angle at the Earth's centre between the sub-satellite point and the edge (degrees).
+    pub radius: f64,
+}
+
+impl Footprint {
+    /// The footprint of a satellite `alt` km above `lat`, `lon`.
+    pub fn new(lat: f64, lon: f64, alt: f64, min_elevation: f64) -> Self {
+        let elevation = min_elevation.to_radians();
+        let ratio = EARTH_RADIUS_KM / (EARTH_RADIUS_KM + alt.max(0.0));
+        let radius = ((ratio * elevation.cos()).acos() - elevation).max(0.0).to_degrees();
+
+        Footprint {
+            lat,
+            lon,
+            min_elevation,
+            radius,
+        }
+    }
+
+    /// The footprint's radius along the ground (km).
+    pub fn radius_km(&self) -> f64 {
+        self.radius.to_radians() * EARTH_RADIUS_KM
+    }
+
+    pub fn contains(&self, lat: f64, lon: f64) -> bool {
+        central_angle(self.lat, self.lon, lat, lon) <= self.radius
+    }
+
+    /// The edge as (lon, lat) polylines, split where it crosses the antimeridian.
+    pub fn outline(&self) -> Vec<Vec<(f64, f64)>> {
+        let steps = (360.0 / OUTLINE_STEP_DEGREES) as usize;
+        let points: Vec<(f64, f64)> = (0..=steps)
+            .map(|step| {
+                let (lat, lon) = destination(self.lat, self.lon, step as f64 * OUTLINE_STEP_DEGREES, self.radius);
+                (lon, lat)
+            })
+            .collect();
+
+        split_at_antimeridian(&points)
+    }
+
+    /// (lat, lon) points spread evenly over the footprint, centre and edge included.
+    pub fn sample_points(&self) -> Vec<(f64, f64)> {
+        let rings = (self.radius / SAMPLE_SPACING_DEGREES).ceil() as usize;
+        let mut points = vec![(self.lat, self.lon)];
+        for ring in 1..=rings {
+            let distance = self.radius * ring as f64 / rings as f64;
+            let circumference = 360.0 * distance.to_radians().sin();
+            let bearings = (circumference / SAMPLE_SPACING_DEGREES).ceil().max(6.0) as usize;
+            for bearing in 0..bearings {
+                points.push(destination(self.lat, self.lon, 360.0 * bearing as f64 / bearings as f64, distance));
+            }
+        }
+
+        points
+    }
+
+    /// The countries `lookup` finds at any of [`Footprint::sample_points`], sorted.
+    pub fn countries(&self, lookup: impl Fn(f64, f64) -> Option<String>) -> Vec<String> {
+        let mut countries: Vec<String> = self
+            .sample_points()
+            .into_iter()
+            .filter_map(|(lat, lon)| lookup(lat, lon))
+            .collect();
+        countries.sort();
+        countries.dedup();
+        countries
+    }
+}
+
+/// Angle at the Earth's centre between two points (degrees), by the haversine formula.
+pub fn central_angle(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let (lat1, lat2) = (lat1.to_radians(), lat2.to_radians());
+    let half_dlat = (lat2 - lat1) / 2.0;
+    let half_dlon = (lon2 - lon1).to_radians() / 2.0;
+    let h = half_dlat.sin().powi(2) + lat1.cos() * lat2.cos() * half_dlon.sin().powi(2);
+
+    (2.0 * h.sqrt().min(1.0).asin()).to_degrees()
+}
+
+/// Initial direction of the great circle from the first point to the second (degrees clockwise
+/// from north, in [0, 360)).
+pub fn bearing(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let (lat1, lat2) = (lat1.to_radians(), lat2.to_radians());
+    let dlon = (lon2 - lon1).to_radians();
+    let y = dlon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * dlon.cos();
+
+    y.atan2(x).to_degrees().rem_euclid(360.0)
+}
+
+/// The point `distance` degrees of arc from `lat`, `lon` along `bearing` (degrees from north),
+/// as (lat, lon) with the longitude in [-180, 180).
+pub fn destination(lat: f64, lon: f64, bearing: f64, distance: f64) -> (f64, f64) {
+    let (lat, bearing, distance) = (lat.to_radians(), bearing.to_radians(), distance.to_radians());
+    let end_lat = (lat.sin() * distance.cos() + lat.cos() * distance.sin() * bearing.cos()).asin();
+    let delta_lon = (bearing.sin() * distance.sin() * lat.cos()).atan2(distance.cos() - lat.sin() * end_lat.sin());
+
+    let end_lon = (lon + delta_lon.to_degrees() + 180.0).rem_euclid(360.0) - 180.0;
+    (end_lat.to_degrees(), end_lon)
+}
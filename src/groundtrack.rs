@@ -0,0 +1,111 @@
+use crate::orbit::{period_minutes, Propagator};
+use crate::provider::Position;
+use chrono::{DateTime, Duration, Utc};
+
+/// Spacing between predicted ground-track points.
+pub const STEP_SECONDS: i64 = 30;
+/// Spacing between time ticks along the predicted track.
+pub const TICK_MINUTES: i64 = 10;
+
+/// A ground track ready to draw on a longitude/latitude map.
+#[derive(Debug, Clone, Default)]
+pub struct GroundTrack {
+    /// Polylines of (lon, lat) points, split where the track crosses the antimeridian.
+    pub segments: Vec<Vec<(f64, f64)>>,
+    /// (time, lon, lat) every [`TICK_MINUTES`].
+    pub ticks: Vec<(DateTime<Utc>, f64, f64)>,
+}
+
+impl GroundTrack {
+    /// Predicts the next `orbits` orbits from `start`.
+    pub fn predict(propagator: &dyn Propagator, start: DateTime<Utc>, orbits: f64) -> Self {
+        let positions = predict_positions(propagator, start, orbits);
+        let end = match positions.last() {
+            Some(last) => DateTime::from_timestamp(last.timestamp as i64, 0).unwrap_or(start),
+            None => return GroundTrack::default(),
+        };
+        let points: Vec<(f64, f64)> = positions.iter().map(|position| (position.lon, position.lat)).collect();
+
+        // Ticks fall on whole multiples of TICK_MINUTES of UTC.
+        let tick_seconds = TICK_MINUTES * 60;
+        let mut ticks = Vec::new();
+        let mut tick = DateTime::from_timestamp((start.timestamp() / tick_seconds + 1) * tick_seconds, 0)
+            .unwrap_or(start);
+        while tick <= end {
+            if let Ok(point) = propagator.geodetic_at(tick) {
+                ticks.push((tick, point.lon, point.lat));
+            }
+            tick += Duration::seconds(tick_seconds);
+        }
+
+        GroundTrack {
+            segments: split_at_antimeridian(&points),
+            ticks,
+        }
+    }
+
+    /// A track through already-visited positions, without ticks.
+    pub fn from_history(history: &[Position]) -> Self {
+        let points: Vec<(f64, f64)> = history.iter().map(|position| (position.lon, position.lat)).collect();
+
+        GroundTrack {
+            segments: split_at_antimeridian(&points),
+            ticks: Vec::new(),
+        }
+    }
+}
+
+/// Positions every [`STEP_SECONDS`] over the next `orbits` orbits from `start`.
+pub fn predict_positions(propagator: &dyn Propagator, start: DateTime<Utc>, orbits: f64) -> Vec<Position> {
+    let period = match propagator.state_at(start) {
+        Ok(state) => period_minutes(&state),
+        Err(_) => return Vec::new(),
+    };
+    let end = start + Duration::seconds((period * 60.0 * orbits) as i64);
+
+    let mut positions = Vec::new();
+    let mut time = start;
+    while time <= end {
+        if let Ok(point) = propagator.geodetic_at(time) {
+            positions.push(Position {
+                lat: point.lat,
+                lon: point.lon,
+                alt: point.alt,
+                timestamp: time.timestamp() as f64,
+            });
+        }
+        time += Duration::seconds(STEP_SECONDS);
+    }
+
+    positions
+}
+
+/// Splits a (lon, lat) polyline wherever it wraps from +180° to -180° (or back),
+/// ending and starting the pieces at the interpolated crossing latitude.
+pub fn split_at_antimeridian(points: &[(f64, f64)]) -> Vec<Vec<(f64, f64)>> {
+    let mut segments = Vec::new();
+    let mut current: Vec<(f64, f64)> = Vec::new();
+
+    for &(lon, lat) in points {
+        if let Some(&(prev_lon, prev_lat)) = current.last() {
+            let delta = lon - prev_lon;
+            if delta.abs() > 180.0 {
+                // Unwrap the longitude so the step is short, then find where it hits the edge.
+                let edge = if delta < 0.0 { 180.0 } else { -180.0 };
+                let unwrapped = lon + 2.0 * edge;
+                let fraction = (edge - prev_lon) / (unwrapped - prev_lon);
+                let crossing_lat = prev_lat + fraction * (lat - prev_lat);
+
+                current.push((edge, crossing_lat));
+                segments.push(std::mem::take(&mut current));
+                current.push((-edge, crossing_lat));
+            }
+        }
+        current.push((lon, lat));
+    }
+    if current.len() > 1 {
+        segments.push(current);
+    }
+
+    segments
+}
@@ -0,0 +1,55 @@
+use crate::error::IssError;
+use serde_json::Value;
+use std::fmt;
+use std::time::Duration;
+
+/// How long a request may take before it is abandoned, unless configured otherwise.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Fetches the body of a URL. Everything that talks to an API goes through one of these, so
+/// tests and alternative transports can stand in for the network.
+pub trait HttpClient: fmt::Debug + Send + Sync {
+    /// GETs `url` and returns the body, treating non-2xx statuses as errors.
+    fn get(&self, url: &str) -> Result<String, IssError>;
+
+    /// GETs `url` and parses the body as JSON.
+    fn get_json(&self, url: &str) -> Result<Value, IssError> {
+        Ok(serde_json::from_str(&self.get(url)?)?)
+    }
+}
+
+/// [`HttpClient`] backed by a blocking reqwest client.
+#[derive(Debug, Clone)]
+pub struct ReqwestClient {
+    client: reqwest::blocking::Client,
+}
+
+impl ReqwestClient {
+    /// A client giving up on requests that take longer than `timeout`.
+    pub fn new(timeout: Duration) -> Result<Self, IssError> {
+        let client = reqwest::blocking::Client::builder().timeout(timeout).build()?;
+        Ok(ReqwestClient { client })
+    }
+}
+
+impl Default for ReqwestClient {
+    fn default() -> Self {
+        ReqwestClient::new(DEFAULT_TIMEOUT).unwrap_or_else(|_| ReqwestClient {
+            client: reqwest::blocking::Client::new(),
+        })
+    }
+}
+
+impl HttpClient for ReqwestClient {
+    fn get(&self, url: &str) -> Result<String, IssError> {
+        let res = self.client.get(url).send()?;
+        if !res.status().is_success() {
+            return Err(IssError::HttpStatus {
+                url: url.to_string(),
+                status: res.status().as_u16(),
+            });
+        }
+
+        Ok(res.text()?)
+    }
+}
@@ -1,65 +1,107 @@
 #[cfg(not(target_arch = "wasm32"))]
-use rgeo::record::{Nvec, Record};
-#[cfg(not(target_arch = "wasm32"))]
 use rgeo::search;
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::footprint::{bearing, central_angle};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::orbit::{distance_km, Geodetic, OrbitalElements, Propagator, EARTH_RADIUS_KM};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::passes::compass_point;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::http::{HttpClient, ReqwestClient};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::provider::{fetch_with_fallback, PositionProvider, WhereTheIss};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Arc;
+
+use crate::error::IssError;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::position::PositionSource;
+use crate::position::Position;
+use crate::regions::locate;
+use chrono::DateTime;
+use std::fmt;
+use crate::weather::{weather_url, Weather, WeatherUnits, OPEN_METEO_URL};
 use serde_json::Value;
-use std::io::Read;
-use std::str::FromStr;
 use std::string::String;
 use serde::{Deserialize, Serialize};
 
-pub const WEATHER_ASCII_SUN: &str = "
-   | \n
- ~ 0 ~\n
-   |\n
-";
-
-pub const WEATHER_ASCII_CLOUDS: &str = "
-    ~~~~\n
-   (░░░░) \n
- (░░░░░░)\n
- (░░░░░)\n
-";
-
-pub const WEATHER_ASCII_RAIN: &str = "
- !  !\n
-  '   ' '! \n
- ' ' ! !\n
- !  !\n
-";
-
-pub const WEATHER_ASCII_WIND: &str = "
- ~  ~ ~\n
-   ~  ~  ~\n
- ~ ~ ~ ~\n
-";
-
-pub const WEATHER_ASCII_OUTER_SPACE: &str = "
- *       *\n
-      *   \n
-   *    \n
-";
+/// open-notify's list of the people currently in space.
+pub const OPEN_NOTIFY_CREW_URL: &str = "http://api.open-notify.org/astros.json";
+
+/// Positions kept in [`Iss::pos_data`]; about seven hours at the TUI refresh rate.
+pub const MAX_POS_DATA: usize = 4500;
+
+/// Crossings kept in [`Iss::crossings`]; a day's worth at roughly twenty per orbit.
+pub const MAX_CROSSINGS: usize = 300;
+
+/// The ground track passing from one country, sea or ocean into another.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Crossing {
+    /// Time of the first position in `to` (Unix seconds).
+    pub time: f64,
+    pub from: String,
+    pub to: String,
+}
+
+impl fmt::Display for Crossing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match DateTime::from_timestamp(self.time.floor() as i64, 0) {
+            Some(time) => write!(f, "Entered {} {}", self.to, time.format("%H:%M:%S UTC")),
+            None => write!(f, "Entered {}", self.to),
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Iss {
     pub lat: f64,
     pub lon: f64,
     pub alt: f64,
-    #[cfg(not(target_arch = "wasm32"))]
     pub time: f64,
-    #[cfg(not(target_arch = "wasm32"))]
     pub country: String,
-    #[cfg(not(target_arch = "wasm32"))]
-    pub pos_data: Vec<(f64, f64)>,
+    pub pos_data: Vec<Position>,
+    /// Where the ground track has changed country, sea or ocean, oldest first.
+    #[serde(default)]
+    pub crossings: Vec<Crossing>,
+    /// Whether the latest position made the last of `crossings`.
+    #[serde(default)]
+    pub crossed: bool,
+    /// The last region a lookup found, which crossings are measured from. Unlike `country`, it
+    /// is kept when a lookup fails.
+    #[serde(default)]
+    pub last_region: Option<String>,
     #[cfg(not(target_arch = "wasm32"))]
     pub prev_alt: f64,
     #[cfg(not(target_arch = "wasm32"))]
     pub alt_perigee_apogee: String,
+    /// Osculating elements at the last update, when a propagator is available.
     #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    pub elements: Option<OrbitalElements>,
     pub crew: String,
+    pub weather: Option<Weather>,
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip, default = "default_provider")]
+    pub provider: Box<dyn PositionProvider>,
+    /// Used to keep the position moving when the provider fails.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    pub propagator: Option<Arc<dyn Propagator>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub source: PositionSource,
+    /// Independent prediction (the OEM) that live positions are compared against.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    pub cross_check: Option<Arc<dyn Propagator>>,
+    /// Distance between the last live position and the `cross_check` prediction (km).
     #[cfg(not(target_arch = "wasm32"))]
-    pub weather: String,
+    pub cross_check_km: Option<f64>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn default_provider() -> Box<dyn PositionProvider> {
+    Box::new(WhereTheIss::default())
 }
 
 impl Iss {
@@ -69,259 +111,331 @@ impl Iss {
             lat: 0.0,
             lon: 0.0,
             alt: 0.0,
-            #[cfg(not(target_arch = "wasm32"))]
             time: 0.0,
-            #[cfg(not(target_arch = "wasm32"))]
             pos_data: Vec::new(),
+            crossings: Vec::new(),
+            crossed: false,
+            last_region: None,
             #[cfg(not(target_arch = "wasm32"))]
             prev_alt: 0.0,
-            #[cfg(not(target_arch = "wasm32"))]
             country: String::new(),
             #[cfg(not(target_arch = "wasm32"))]
             alt_perigee_apogee: String::new(),
             #[cfg(not(target_arch = "wasm32"))]
+            elements: None,
             crew: String::new(),
+            weather: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            provider: default_provider(),
             #[cfg(not(target_arch = "wasm32"))]
-            weather: String::new(),
+            propagator: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            source: PositionSource::Live,
+            #[cfg(not(target_arch = "wasm32"))]
+            cross_check: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            cross_check_km: None,
         }
     }
 
+    /// Constructs a new instance of [`Iss`] that reads its position from `provider`.
     #[cfg(not(target_arch = "wasm32"))]
-    pub fn update_crew(&mut self) {
-        let current_crew = get_crew().unwrap();
-        self.crew = current_crew.join("\n");
+    pub fn with_provider(provider: Box<dyn PositionProvider>) -> Self {
+        let mut iss = Iss::new();
+        iss.provider = provider;
+        iss
     }
 
+    /// Refreshes the crew list. On error the previous list is kept.
     #[cfg(not(target_arch = "wasm32"))]
-    pub fn update_weather(&mut self) {
-        let weather = get_weather(self.lat, self.lon).unwrap();
+    pub fn update_crew(&mut self) -> Result<(), IssError> {
+        let current_crew = get_crew(&ReqwestClient::default(), OPEN_NOTIFY_CREW_URL)?;
+        self.crew = current_crew.join("\n");
+        Ok(())
+    }
 
-        self.weather = weather;
+    /// Refreshes the weather below the ISS. On error the previous report is kept.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn update_weather(&mut self) -> Result<(), IssError> {
+        let client = ReqwestClient::default();
+        self.weather = Some(get_weather(&client, OPEN_METEO_URL, self.lat, self.lon, &WeatherUnits::default())?);
+        Ok(())
     }
-        // WASM stubs (so the interface is consistent)
+    /// Refreshes the crew list with the browser's fetch. On error the previous list is kept.
     #[cfg(target_arch = "wasm32")]
-    pub fn update_crew(&mut self) {
-        // No-op for WASM or simplified implementation
+    pub async fn update_crew_async(&mut self) -> Result<(), IssError> {
+        self.crew = get_crew_async(OPEN_NOTIFY_CREW_URL).await?.join("\n");
+        Ok(())
     }
 
+    /// Refreshes the weather below the ISS with the browser's fetch. On error the previous
+    /// report is kept.
     #[cfg(target_arch = "wasm32")]
-    pub fn update_weather(&mut self) {
-        // No-op for WASM or simplified implementation
+    pub async fn update_weather_async(&mut self) -> Result<(), IssError> {
+        self.weather = Some(get_weather_async(OPEN_METEO_URL, self.lat, self.lon, &WeatherUnits::default()).await?);
+        Ok(())
     }
 
-    /// Set running to false to quit the application.
+    /// Fetches a new position from the provider, falling back to the propagator
+    /// when the provider fails. If both fail the previous position is kept.
     #[cfg(not(target_arch = "wasm32"))]
-    pub fn update_position(&mut self) {
-        let new_position = get_position().unwrap();
-        self.prev_alt = self.alt;
-        self.lat = new_position.0;
-        self.lon = new_position.1;
-        self.alt = new_position.2;
-        self.time = new_position.3;
-        self.country = new_position.4;
-        self.pos_data.push((new_position.0, new_position.1));
-        if self.prev_alt > self.alt {
-            self.alt_perigee_apogee = String::from("Approaching Perigee");
-        } else {
-            self.alt_perigee_apogee = String::from("Approaching Apogee");
-        }
+    pub fn update_position(&mut self) -> Result<(), IssError> {
+        let (new_position, source) =
+            fetch_with_fallback(self.provider.as_mut(), self.propagator.as_deref())?;
+        self.set_position(new_position, source);
 
-        // these numbers are reported differently across the internet: 370-460 km as the altitude
-        // 413 and 422 reported as the perigee and apogee
-        if self.alt.floor() > 429.0 {
-            self.alt_perigee_apogee = String::from("Apogee Reached");
-        }
+        Ok(())
+    }
+
+    /// Moves the ISS to `new_position` and refreshes everything derived from it.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_position(&mut self, new_position: Position, source: PositionSource) {
+        let country = get_country(new_position.lat, new_position.lon).ok();
+        self.set_position_over(new_position, source, country);
+    }
 
-        if self.alt.floor() < 372.0 {
-            self.alt_perigee_apogee = String::from("Perigee Reached");
+    /// Moves the ISS to `new_position` over a region that is already known, such as one
+    /// recorded with a replayed sample, instead of looking it up again. `None` is a failed lookup.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_position_over(&mut self, new_position: Position, source: PositionSource, country: Option<String>) {
+        self.source = source;
+        self.cross_check_km = match (&self.cross_check, source) {
+            (Some(reference), PositionSource::Live) => cross_check_distance(reference.as_ref(), &new_position),
+            _ => None,
+        };
+        self.prev_alt = self.alt;
+        self.lat = new_position.lat;
+        self.lon = new_position.lon;
+        self.alt = new_position.alt;
+        self.time = new_position.timestamp;
+        self.set_country(country);
+        self.pos_data.push(new_position);
+        if self.pos_data.len() > MAX_POS_DATA {
+            self.pos_data.drain(..self.pos_data.len() - MAX_POS_DATA);
         }
+        self.elements = self.propagator.as_ref().and_then(|propagator| {
+            let time = DateTime::from_timestamp(new_position.timestamp.floor() as i64, 0)?;
+            propagator.state_at(time).ok().map(|state| OrbitalElements::from_state(&state))
+        });
+        self.alt_perigee_apogee = match &self.elements {
+            Some(elements) if elements.is_ascending() => String::from("Approaching Apogee"),
+            Some(_) => String::from("Approaching Perigee"),
+            // Without an orbit, all that is known is which way the altitude moved.
+            None if self.prev_alt > self.alt => String::from("Descending"),
+            None => String::from("Climbing"),
+        };
     }
 #[cfg(target_arch = "wasm32")]
-pub async fn update_position_async(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+pub async fn update_position_async(&mut self) -> Result<(), IssError> {
     let new_position = get_position_async().await?; // This can fail, so propagate the error
-    self.lat = new_position.0;
-    self.lon = new_position.1;
-    self.alt = new_position.2;
+    self.set_position(new_position);
     
     Ok(()) // Return success
 }
 
+    /// Moves the ISS to `new_position` and records it in [`Iss::pos_data`].
+    #[cfg(target_arch = "wasm32")]
+    pub fn set_position(&mut self, new_position: Position) {
+        self.lat = new_position.lat;
+        self.lon = new_position.lon;
+        self.alt = new_position.alt;
+        self.time = new_position.timestamp;
+        self.set_country(get_country(new_position.lat, new_position.lon).ok());
+        self.pos_data.push(new_position);
+        if self.pos_data.len() > MAX_POS_DATA {
+            self.pos_data.drain(..self.pos_data.len() - MAX_POS_DATA);
+        }
+    }
+
+    /// Sets the region below a new position, recording a [`Crossing`] when it differs from
+    /// the last region found. Nothing is recorded for the first position, or when the lookup
+    /// failed: the next region found is compared with the one before the failure.
+    fn set_country(&mut self, country: Option<String>) {
+        self.crossed = false;
+        let Some(country) = country else {
+            self.country = String::from("Unknown Country");
+            return;
+        };
+        match self.last_region.replace(country.clone()) {
+            Some(from) if from != country => {
+                self.crossings.push(Crossing {
+                    time: self.time,
+                    from,
+                    to: country.clone(),
+                });
+                if self.crossings.len() > MAX_CROSSINGS {
+                    self.crossings.drain(..self.crossings.len() - MAX_CROSSINGS);
+                }
+                self.crossed = true;
+            }
+            _ => {}
+        }
+        self.country = country;
+    }
+
+    /// The crossing made by the latest position, if it made one.
+    pub fn crossing(&self) -> Option<&Crossing> {
+        self.crossings.last().filter(|_| self.crossed)
+    }
 }
 
+/// Distance between `position` and where `reference` predicts the ISS at the same time (km).
 #[cfg(not(target_arch = "wasm32"))]
-pub fn get_position() -> Result<(f64, f64, f64, f64, String), Box<dyn std::error::Error>> {
-    //let mut res = reqwest::blocking::get("http://api.open-notify.org/iss-now.json")?;
-    // https://api.wheretheiss.at/v1/satellites/25544
-    let mut res = reqwest::blocking::get("https://api.wheretheiss.at/v1/satellites/25544")?;
-    let mut body = String::new();
-    res.read_to_string(&mut body)?;
-
-    let json: Value = match serde_json::from_str(&body) {
-        Ok(json) => json,
-        Err(err) => return Err(Box::new(err)),
+fn cross_check_distance(reference: &dyn Propagator, position: &Position) -> Option<f64> {
+    let time = DateTime::from_timestamp(position.timestamp.floor() as i64, 0)?;
+    let predicted = reference.geodetic_at(time).ok()?;
+    let live = Geodetic {
+        lat: position.lat,
+        lon: position.lon,
+        alt: position.alt,
     };
 
-    let latitude: f64 = json["latitude"].as_f64().expect("Desire a number");
-    let longitude: f64 = json["longitude"].as_f64().expect("Desire a number");
-    let altitude: f64 = json["altitude"].as_f64().expect("Desire a number");
-    let timestamp: f64 = json["timestamp"].as_f64().expect("Desire a number");
-    let country: String = match get_country(latitude, longitude) {
-        Ok(country) => country,
-        Err(e) => "Unknown Country".to_string(),
-    };
+    Some(distance_km(&live, &predicted))
+}
 
-    Ok((latitude, longitude, altitude, timestamp, country))
+/// Fetches the current position from the wheretheiss.at satellite endpoint at `url`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn get_position(client: &dyn HttpClient, url: &str) -> Result<Position, IssError> {
+    let json = client.get_json(url)?;
+
+    Ok(Position {
+        lat: json_f64(&json, "/latitude")?,
+        lon: json_f64(&json, "/longitude")?,
+        alt: json_f64(&json, "/altitude")?,
+        timestamp: json_f64(&json, "/timestamp")?,
+    })
 }
 
-#[cfg(target_arch = "wasm32")]
-async fn get_position_async() -> Result<(f64, f64, f64, String), Box<dyn std::error::Error>> {
-    let response = reqwest::get("https://api.wheretheiss.at/v1/satellites/25544").await?;
-    let json: Value = response.json().await?;
-    
-    let lat = json["latitude"].as_f64().unwrap();
-    let lon = json["longitude"].as_f64().unwrap();
-    let alt = json["altitude"].as_f64().unwrap();
-    
-    // No country lookup in WASM for now (rgeo doesn't work in WASM)
-    let country = String::from("Unknown");
-    
-    Ok((lat, lon, alt, country))
+/// Reads a number at `pointer` (e.g. `/current/temperature`) from `json`.
+pub(crate) fn json_f64(json: &Value, pointer: &str) -> Result<f64, IssError> {
+    json.pointer(pointer)
+        .and_then(Value::as_f64)
+        .ok_or_else(|| IssError::MissingField(pointer.to_string()))
 }
 
-#[cfg(not(target_arch = "wasm32"))]
-pub fn get_country(lat: f64, lon: f64) -> Result<String, Box<dyn std::error::Error>> {
-    let latitude = lat;
-    let longitude = lon;
-
-    let default_record = Record {
-        name: String::from("Unknown Location"),
-        nvec: Nvec::from_lat_long(0.0, 0.0),
-        country: String::from("Unknown Country"),
-    };
+/// GETs `url` with the browser's fetch and returns the body, treating non-2xx statuses as errors.
+#[cfg(target_arch = "wasm32")]
+async fn get_async(url: &str) -> Result<String, IssError> {
+    let response = reqwest::get(url).await?;
+    if !response.status().is_success() {
+        return Err(IssError::HttpStatus {
+            url: url.to_string(),
+            status: response.status().as_u16(),
+        });
+    }
 
-    let rgeo_result = search(latitude as f32, longitude as f32).unwrap_or((0.0, &default_record));
+    Ok(response.text().await?)
+}
 
-    //let flag = flag(rgeo_result.1.country.as_str()).unwrap_or(String::from("Unknown Country"));
-    //let countryString = String::from(rgeo_result.1.country.as_str()) + "\n" + flag.as_str();
-    let countryString = String::from(rgeo_result.1.country.as_str());
+/// Fetches the current position from wheretheiss.at with the browser's fetch.
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn get_position_async() -> Result<Position, IssError> {
+    let json: Value = serde_json::from_str(&get_async("https://api.wheretheiss.at/v1/satellites/25544").await?)?;
+
+    Ok(Position {
+        lat: json_f64(&json, "/latitude")?,
+        lon: json_f64(&json, "/longitude")?,
+        alt: json_f64(&json, "/altitude")?,
+        timestamp: json_f64(&json, "/timestamp")?,
+    })
+}
 
-    Ok(countryString)
+/// The people in space, from the open-notify `astros.json` endpoint at `url`, with the browser's fetch.
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn get_crew_async(url: &str) -> Result<Vec<String>, IssError> {
+    crew_from_json(&serde_json::from_str(&get_async(url).await?)?)
 }
 
+/// The current weather at a point, from the Open-Meteo server at `url`, with the browser's fetch.
 #[cfg(target_arch = "wasm32")]
-pub fn get_country(lat: f64, lon: f64) -> Result<String, Box<dyn std::error::Error>> {
-    let latitude = lat;
-    let longitude = lon;
-    let countryString = String::from("The Undiscovered Country");
+pub(crate) async fn get_weather_async(
+    url: &str,
+    lat: f64,
+    lon: f64,
+    units: &WeatherUnits,
+) -> Result<Weather, IssError> {
+    Ok(Weather::from_open_meteo(&get_async(&weather_url(url, lat, lon, units)).await?)?)
+}
 
-    Ok(countryString)
+/// The country under the point, or else the sea or ocean, from the outlines in [`crate::regions`].
+pub fn get_country(lat: f64, lon: f64) -> Result<String, IssError> {
+    locate(lat, lon)
+        .map(|region| region.name.clone())
+        .ok_or_else(|| IssError::Geocode(format!("no region found at {lat}, {lon}")))
 }
 
+/// A populated place near a point, shown under the country in the Tracker.
 #[cfg(not(target_arch = "wasm32"))]
-pub fn get_crew() -> Result<Vec<String>, Box<dyn std::error::Error>> {
-    let mut res = reqwest::blocking::get("http://api.open-notify.org/astros.json")?;
-    let mut body = String::new();
-    res.read_to_string(&mut body)?;
-
-    let json: Value = match serde_json::from_str(&body) {
-        Ok(json) => json,
-        Err(err) => return Err(Box::new(err)),
-    };
-
-    let new_array = json["people"].as_array().unwrap();
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NearestPlace {
+    pub name: String,
+    pub country: String,
+    /// Great-circle distance from the point (km).
+    pub distance_km: f64,
+    /// Direction of the place from the point (degrees clockwise from north).
+    pub bearing: f64,
+}
 
-    let mut crew_member_list = Vec::new();
-    for val in new_array.into_iter() {
-        crew_member_list.push(val["name"].to_string());
+#[cfg(not(target_arch = "wasm32"))]
+impl fmt::Display for NearestPlace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, {}: {:.0} km {}",
+            self.name,
+            self.country,
+            self.distance_km,
+            compass_point(self.bearing)
+        )
     }
-
-    Ok((crew_member_list))
 }
-#[cfg(not(target_arch = "wasm32"))]
-pub fn get_weather(lat: f64, lon: f64) -> Result<String, Box<dyn std::error::Error>> {
-    let constructed_url = format!("https://api.open-meteo.com/v1/forecast?latitude={lat}&longitude={lon}&current=temperature,weather_code").to_string();
-
-    let mut res = reqwest::blocking::get(constructed_url)?;
-    let mut body = String::new();
-    res.read_to_string(&mut body)?;
-
-    let json: Value = match serde_json::from_str(&body) {
-        Ok(json) => json,
-        Err(err) => return Err(Box::new(err)),
-    };
-
-    let mut forecast = json["current"]["temperature"].to_string();
-
-    let weather_code = json["current"]["weather_code"].to_string();
-
-    let wmo_forecast = get_wmo_code(&weather_code);
-
-    forecast.push_str(" degrees");
-    forecast.push_str("\n");
-    forecast.push_str(&wmo_forecast);
 
-    let ascii_weather = get_weather_ascii(&weather_code);
-
-    forecast.push_str("\n");
-    forecast.push_str(&ascii_weather);
-
-    Ok(forecast)
+/// The closest place in `rgeo`'s database. It only holds populated places, so over the open
+/// ocean the answer can be thousands of kilometres away.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn nearest_place(lat: f64, lon: f64) -> Option<NearestPlace> {
+    let (_, record) = search(lat as f32, lon as f32)?;
+    // Places are stored as unit n-vectors.
+    let (x, y, z) = (record.nvec.x as f64, record.nvec.y as f64, record.nvec.z as f64);
+    let place_lat = z.atan2(x.hypot(y)).to_degrees();
+    let place_lon = y.atan2(x).to_degrees();
+
+    Some(NearestPlace {
+        name: record.name.clone(),
+        country: record.country.clone(),
+        distance_km: central_angle(lat, lon, place_lat, place_lon).to_radians() * EARTH_RADIUS_KM,
+        bearing: bearing(lat, lon, place_lat, place_lon),
+    })
 }
 
+/// The people in space, from the open-notify `astros.json` endpoint at `url`.
 #[cfg(not(target_arch = "wasm32"))]
-pub fn get_weather_ascii(weather_code: &String) -> String {
-    let code_int = match weather_code.parse::<i32>() {
-        Ok(num) => num,
-        Err(_) => 1001,
-    };
+pub fn get_crew(client: &dyn HttpClient, url: &str) -> Result<Vec<String>, IssError> {
+    crew_from_json(&client.get_json(url)?)
+}
 
-    // Match the integer code to weather condition
-    let condition = match code_int {
-        0 => WEATHER_ASCII_SUN,
-        1..=3 => WEATHER_ASCII_SUN,
-        4..=8 => WEATHER_ASCII_CLOUDS,
-        51..=55 => WEATHER_ASCII_RAIN,
-        56..=57 => WEATHER_ASCII_RAIN,
-        61..=65 => WEATHER_ASCII_RAIN,
-        66..=67 => WEATHER_ASCII_RAIN,
-        71..=75 => WEATHER_ASCII_RAIN,
-        77 => WEATHER_ASCII_RAIN,
-        80..=82 => WEATHER_ASCII_RAIN,
-        85..=86 => WEATHER_ASCII_RAIN,
-        95 => WEATHER_ASCII_RAIN,
-        96..=99 => WEATHER_ASCII_RAIN,
-        _ => WEATHER_ASCII_OUTER_SPACE,
-    };
+/// The names in an `astros.json` response.
+fn crew_from_json(json: &Value) -> Result<Vec<String>, IssError> {
+    let new_array = json["people"]
+        .as_array()
+        .ok_or_else(|| IssError::MissingField(String::from("/people")))?;
 
-    let final_string = String::from_str(condition).unwrap();
+    let mut crew_member_list = Vec::new();
+    for val in new_array.iter() {
+        crew_member_list.push(val["name"].as_str().unwrap_or_default().to_string());
+    }
 
-    final_string
+    Ok(crew_member_list)
 }
 
+/// The current weather at a point, from the Open-Meteo server at `url`.
 #[cfg(not(target_arch = "wasm32"))]
-fn get_wmo_code(weather_code: &String) -> String {
-    let code_int = match weather_code.parse::<i32>() {
-        Ok(num) => num,
-        Err(_) => 1001,
-    };
-
-    // Match the integer code to weather condition
-    let condition = match code_int {
-        0 => "Clear sky".to_string(),
-        1..=3 => "Mainly clear, partly cloudy, or overcast".to_string(),
-        4..=8 => "Fog".to_string(),
-        51..=55 => "Drizzle (Light, moderate, or dense)".to_string(),
-        56..=57 => "Freezing Drizzle (Light or dense)".to_string(),
-        61..=65 => "Rain (Slight, moderate, or heavy)".to_string(),
-        66..=67 => "Freezing Rain (Light or heavy)".to_string(),
-        71..=75 => "Snowfall (Slight, moderate, or heavy)".to_string(),
-        77 => "Snow grains".to_string(),
-        80..=82 => "Rain showers (Slight, moderate, or violent)".to_string(),
-        85..=86 => "Snow showers (Slight or heavy)".to_string(),
-        95 => "Thunderstorm (Slight or moderate)".to_string(),
-        96..=99 => "Thunderstorm with slight or heavy hail".to_string(),
-        _ => "Unknown weather code".to_string(),
-    };
-
-    return condition;
+pub fn get_weather(
+    client: &dyn HttpClient,
+    url: &str,
+    lat: f64,
+    lon: f64,
+    units: &WeatherUnits,
+) -> Result<Weather, IssError> {
+    Ok(Weather::from_open_meteo(&client.get(&weather_url(url, lat, lon, units))?)?)
 }
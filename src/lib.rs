@@ -1,5 +1,43 @@
 // Common modules that should work on all targets
+pub mod error;
 pub mod iss;
+pub mod position;
+pub mod regions;
+pub mod weather;
+
+// Native-only modules
+#[cfg(not(target_arch = "wasm32"))]
+pub mod config;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod export;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod footprint;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod groundtrack;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod http;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod oem;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod orbit;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod passes;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod provider;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod recorder;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod satellite;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod server;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod solar;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod tle;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod ui;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod worker;
 
 // WASM-specific modules and exports
 #[cfg(target_arch = "wasm32")]
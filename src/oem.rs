@@ -0,0 +1,151 @@
+use crate::error::IssError;
+use crate::orbit::{eme2000_to_ecef, Propagator, StateVector};
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+/// Interpolation degree used when an OEM segment does not specify one.
+pub const DEFAULT_INTERPOLATION_DEGREE: usize = 8;
+
+/// The state vectors between one `META_START`/`META_STOP` block and the next.
+/// Segments are split at maneuvers, so interpolation never crosses them.
+#[derive(Debug, Clone)]
+pub struct OemSegment {
+    pub states: Vec<StateVector>,
+    pub interpolation_degree: usize,
+}
+
+/// The ephemeris segments of an OEM, sorted by epoch.
+#[derive(Debug, Clone, Default)]
+pub struct OemEphemeris {
+    pub segments: Vec<OemSegment>,
+}
+
+impl OemEphemeris {
+    /// Parses an OEM in KVN format, skipping headers, comments and covariance data.
+    pub fn parse(content: &str) -> Self {
+        let mut segments = Vec::new();
+        let mut current = OemSegment {
+            states: Vec::new(),
+            interpolation_degree: DEFAULT_INTERPOLATION_DEGREE,
+        };
+
+        for line in content.lines().map(str::trim) {
+            if line.starts_with("META_START") {
+                if !current.states.is_empty() {
+                    segments.push(current);
+                }
+                current = OemSegment {
+                    states: Vec::new(),
+                    interpolation_degree: DEFAULT_INTERPOLATION_DEGREE,
+                };
+            } else if let Some(degree) = line.strip_prefix("INTERPOLATION_DEGREE") {
+                if let Ok(degree) = degree.trim_start_matches([' ', '=']).trim().parse() {
+                    current.interpolation_degree = degree;
+                }
+            } else if let Some(state) = parse_state_line(line) {
+                current.states.push(state);
+            }
+        }
+        if !current.states.is_empty() {
+            segments.push(current);
+        }
+
+        for segment in &mut segments {
+            segment.states.sort_by_key(|state| state.epoch);
+            segment.states.dedup_by_key(|state| state.epoch);
+        }
+        segments.sort_by_key(|segment| segment.states[0].epoch);
+
+        OemEphemeris { segments }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// First and last epochs covered by the ephemeris.
+    pub fn span(&self) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        let first = self.segments.first()?.states.first()?;
+        let last = self.segments.last()?.states.last()?;
+        Some((first.epoch, last.epoch))
+    }
+
+    /// The segment covering `time`; where segments touch, the later one wins.
+    fn segment_at(&self, time: DateTime<Utc>) -> Option<&OemSegment> {
+        self.segments.iter().rev().find(|segment| {
+            segment.states[0].epoch <= time && time <= segment.states[segment.states.len() - 1].epoch
+        })
+    }
+}
+
+impl Propagator for OemEphemeris {
+    fn name(&self) -> &'static str {
+        "OEM ephemeris"
+    }
+
+    /// EME2000 state at `time`, Lagrange-interpolated over the state vectors
+    /// closest to `time` within its segment.
+    fn state_at(&self, time: DateTime<Utc>) -> Result<StateVector, IssError> {
+        let segment = self.segment_at(time).ok_or_else(|| match self.span() {
+            Some((start, end)) => IssError::Propagation(format!(
+                "{time} is outside the OEM ephemeris ({start} to {end})"
+            )),
+            None => IssError::Propagation(String::from("the OEM ephemeris is empty")),
+        })?;
+
+        let points = (segment.interpolation_degree + 1).min(segment.states.len());
+        let index = segment.states.partition_point(|state| state.epoch <= time);
+        let first = index
+            .saturating_sub(points / 2)
+            .min(segment.states.len() - points);
+        let window = &segment.states[first..first + points];
+
+        let seconds = |epoch: DateTime<Utc>| (epoch - time).num_milliseconds() as f64 / 1000.0;
+        let nodes: Vec<f64> = window.iter().map(|state| seconds(state.epoch)).collect();
+
+        let mut position = [0.0; 3];
+        let mut velocity = [0.0; 3];
+        for (j, state) in window.iter().enumerate() {
+            let weight = lagrange_weight(&nodes, j, 0.0);
+            for axis in 0..3 {
+                position[axis] += weight * state.position[axis];
+                velocity[axis] += weight * state.velocity[axis];
+            }
+        }
+
+        Ok(StateVector {
+            epoch: time,
+            position,
+            velocity,
+        })
+    }
+
+    fn ecef_at(&self, time: DateTime<Utc>) -> Result<[f64; 3], IssError> {
+        let state = self.state_at(time)?;
+        Ok(eme2000_to_ecef(&state.position, time))
+    }
+}
+
+/// The `j`th Lagrange basis polynomial over `nodes`, evaluated at `x`.
+fn lagrange_weight(nodes: &[f64], j: usize, x: f64) -> f64 {
+    nodes
+        .iter()
+        .enumerate()
+        .filter(|&(m, _)| m != j)
+        .map(|(_, &node)| (x - node) / (nodes[j] - node))
+        .product()
+}
+
+fn parse_state_line(line: &str) -> Option<StateVector> {
+    let mut fields = line.split_whitespace();
+    let epoch = NaiveDateTime::parse_from_str(fields.next()?, "%Y-%m-%dT%H:%M:%S%.f").ok()?;
+    let values: Vec<f64> = fields.map(|field| field.parse::<f64>()).collect::<Result<_, _>>().ok()?;
+    if values.len() < 6 {
+        return None;
+    }
+
+    Some(StateVector {
+        epoch: epoch.and_utc(),
+        position: [values[0], values[1], values[2]],
+        velocity: [values[3], values[4], values[5]],
+    })
+}
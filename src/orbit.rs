@@ -0,0 +1,235 @@
+use crate::error::IssError;
+use chrono::{DateTime, Duration, Utc};
+use coord_transforms::prelude::{geo, geo_ellipsoid};
+use nalgebra::{Rotation3, Vector3};
+use std::fmt;
+
+/// Mean Earth radius (km).
+pub const EARTH_RADIUS_KM: f64 = 6371.0;
+/// WGS84 equatorial radius (km).
+pub const EQUATORIAL_RADIUS_KM: f64 = 6378.137;
+/// Earth's gravitational parameter (km³/s²).
+pub const MU_EARTH: f64 = 398_600.4418;
+
+/// An inertial position and velocity (km, km/s) at an epoch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StateVector {
+    pub epoch: DateTime<Utc>,
+    pub position: [f64; 3],
+    pub velocity: [f64; 3],
+}
+
+/// A point above the Earth: latitude and longitude in degrees, altitude in km.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Geodetic {
+    pub lat: f64,
+    pub lon: f64,
+    pub alt: f64,
+}
+
+/// Anything that can predict the ISS state at an arbitrary time without the network.
+pub trait Propagator: fmt::Debug + Send + Sync {
+    /// Short name shown in the UI.
+    fn name(&self) -> &'static str;
+
+    /// Inertial state at `time`.
+    fn state_at(&self, time: DateTime<Utc>) -> Result<StateVector, IssError>;
+
+    /// Earth-fixed position at `time` (km). Assumes `state_at` is in TEME.
+    fn ecef_at(&self, time: DateTime<Utc>) -> Result<[f64; 3], IssError> {
+        let state = self.state_at(time)?;
+        Ok(teme_to_ecef(&state.position, time))
+    }
+
+    /// Sub-satellite point at `time`.
+    fn geodetic_at(&self, time: DateTime<Utc>) -> Result<Geodetic, IssError> {
+        Ok(ecef_to_geodetic(&self.ecef_at(time)?))
+    }
+}
+
+pub fn norm(vector: &[f64; 3]) -> f64 {
+    (vector[0] * vector[0] + vector[1] * vector[1] + vector[2] * vector[2]).sqrt()
+}
+
+/// Orbital period implied by the energy of `state` (minutes).
+pub fn period_minutes(state: &StateVector) -> f64 {
+    let speed = norm(&state.velocity);
+    let semi_major_axis = 1.0 / (2.0 / norm(&state.position) - speed * speed / MU_EARTH);
+    std::f64::consts::TAU * (semi_major_axis.powi(3) / MU_EARTH).sqrt() / 60.0
+}
+
+/// Greenwich mean sidereal time in radians (IAU 1982 model).
+pub fn gmst(time: DateTime<Utc>) -> f64 {
+    let julian_date = time.timestamp_millis() as f64 / 86_400_000.0 + 2_440_587.5;
+    let centuries = (julian_date - 2_451_545.0) / 36_525.0;
+    let seconds = 67_310.548_41
+        + (876_600.0 * 3600.0 + 8_640_184.812_866) * centuries
+        + 0.093_104 * centuries * centuries
+        - 6.2e-6 * centuries * centuries * centuries;
+
+    (seconds % 86_400.0 / 240.0).to_radians().rem_euclid(std::f64::consts::TAU)
+}
+
+/// Rotates a true-equator, mean-equinox (TEME, as produced by SGP4) position
+/// into the Earth-fixed frame. Polar motion and the equation of the equinoxes are ignored.
+pub fn teme_to_ecef(position: &[f64; 3], time: DateTime<Utc>) -> [f64; 3] {
+    (Rotation3::from_axis_angle(&Vector3::z_axis(), -gmst(time)) * Vector3::from(*position)).into()
+}
+
+/// Rotates an EME2000 (J2000) position into the Earth-fixed frame, applying
+/// IAU 1976 precession and Earth rotation. Nutation (< 20 arcsec) is ignored.
+pub fn eme2000_to_ecef(position: &[f64; 3], time: DateTime<Utc>) -> [f64; 3] {
+    let julian_date = time.timestamp_millis() as f64 / 86_400_000.0 + 2_440_587.5;
+    let t = (julian_date - 2_451_545.0) / 36_525.0;
+    let arcsec = |value: f64| (value / 3600.0).to_radians();
+    let zeta = arcsec(2306.2181 * t + 0.30188 * t * t + 0.017998 * t * t * t);
+    let z = arcsec(2306.2181 * t + 1.09468 * t * t + 0.018203 * t * t * t);
+    let theta = arcsec(2004.3109 * t - 0.42665 * t * t - 0.041833 * t * t * t);
+
+    let precession = Rotation3::from_axis_angle(&Vector3::z_axis(), z)
+        * Rotation3::from_axis_angle(&Vector3::y_axis(), -theta)
+        * Rotation3::from_axis_angle(&Vector3::z_axis(), zeta);
+    let earth_rotation = Rotation3::from_axis_angle(&Vector3::z_axis(), -gmst(time));
+
+    (earth_rotation * precession * Vector3::from(*position)).into()
+}
+
+/// Converts an Earth-fixed position (km) to WGS84 latitude, longitude and altitude.
+pub fn ecef_to_geodetic(ecef: &[f64; 3]) -> Geodetic {
+    let ellipsoid = geo_ellipsoid::geo_ellipsoid::new(
+        geo_ellipsoid::WGS84_SEMI_MAJOR_AXIS_METERS,
+        geo_ellipsoid::WGS84_FLATTENING,
+    );
+    let meters = [ecef[0] * 1000.0, ecef[1] * 1000.0, ecef[2] * 1000.0];
+    let lla = geo::ecef2lla(&meters.into(), &ellipsoid);
+
+    Geodetic {
+        lat: lla[0].to_degrees(),
+        lon: lla[1].to_degrees(),
+        alt: lla[2] / 1000.0,
+    }
+}
+
+/// Converts WGS84 latitude, longitude and altitude to an Earth-fixed position (km).
+pub fn geodetic_to_ecef(point: &Geodetic) -> [f64; 3] {
+    let ellipsoid = geo_ellipsoid::geo_ellipsoid::new(
+        geo_ellipsoid::WGS84_SEMI_MAJOR_AXIS_METERS,
+        geo_ellipsoid::WGS84_FLATTENING,
+    );
+    let lla = [point.lat.to_radians(), point.lon.to_radians(), point.alt * 1000.0];
+    let ecef = geo::lla2ecef(&lla.into(), &ellipsoid);
+
+    [ecef[0] / 1000.0, ecef[1] / 1000.0, ecef[2] / 1000.0]
+}
+
+/// Straight-line distance between two points above the Earth (km).
+pub fn distance_km(a: &Geodetic, b: &Geodetic) -> f64 {
+    let to_cartesian = |point: &Geodetic| {
+        let radius = EARTH_RADIUS_KM + point.alt;
+        let (lat, lon) = (point.lat.to_radians(), point.lon.to_radians());
+        Vector3::new(
+            radius * lat.cos() * lon.cos(),
+            radius * lat.cos() * lon.sin(),
+            radius * lat.sin(),
+        )
+    };
+
+    (to_cartesian(a) - to_cartesian(b)).norm()
+}
+
+/// Osculating Keplerian elements of an inertial state. Angles are in degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitalElements {
+    pub epoch: DateTime<Utc>,
+    /// Semi-major axis (km).
+    pub semi_major_axis: f64,
+    pub eccentricity: f64,
+    pub inclination: f64,
+    /// Right ascension of the ascending node.
+    pub raan: f64,
+    pub arg_perigee: f64,
+    pub true_anomaly: f64,
+}
+
+impl OrbitalElements {
+    /// Elements of the two-body orbit passing through `state`. For a near-circular
+    /// orbit the argument of perigee is poorly defined and wanders with every update.
+    pub fn from_state(state: &StateVector) -> Self {
+        let r = Vector3::from(state.position);
+        let v = Vector3::from(state.velocity);
+        let radius = r.norm();
+        let speed = v.norm();
+
+        let h = r.cross(&v);
+        let node = Vector3::z().cross(&h);
+        let e = ((speed * speed - MU_EARTH / radius) * r - r.dot(&v) * v) / MU_EARTH;
+        let eccentricity = e.norm();
+        let semi_major_axis = 1.0 / (2.0 / radius - speed * speed / MU_EARTH);
+
+        let angle = |a: &Vector3<f64>, b: &Vector3<f64>| {
+            (a.dot(b) / (a.norm() * b.norm())).clamp(-1.0, 1.0).acos()
+        };
+        let inclination = (h.z / h.norm()).clamp(-1.0, 1.0).acos();
+        let raan = node.y.atan2(node.x).rem_euclid(std::f64::consts::TAU);
+        let mut arg_perigee = angle(&node, &e);
+        if e.z < 0.0 {
+            arg_perigee = std::f64::consts::TAU - arg_perigee;
+        }
+        let mut true_anomaly = angle(&e, &r);
+        if r.dot(&v) < 0.0 {
+            true_anomaly = std::f64::consts::TAU - true_anomaly;
+        }
+
+        OrbitalElements {
+            epoch: state.epoch,
+            semi_major_axis,
+            eccentricity,
+            inclination: inclination.to_degrees(),
+            raan: raan.to_degrees(),
+            arg_perigee: arg_perigee.to_degrees(),
+            true_anomaly: true_anomaly.to_degrees(),
+        }
+    }
+
+    /// Mean motion (rad/s).
+    fn mean_motion(&self) -> f64 {
+        (MU_EARTH / self.semi_major_axis.powi(3)).sqrt()
+    }
+
+    /// Mean anomaly at the epoch (radians).
+    fn mean_anomaly(&self) -> f64 {
+        let e = self.eccentricity;
+        let nu = self.true_anomaly.to_radians();
+        let eccentric_anomaly = 2.0 * (((1.0 - e) / (1.0 + e)).sqrt() * (nu / 2.0).tan()).atan();
+        (eccentric_anomaly - e * eccentric_anomaly.sin()).rem_euclid(std::f64::consts::TAU)
+    }
+
+    /// First time after the epoch at which the mean anomaly reaches `target` radians.
+    fn next_anomaly(&self, target: f64) -> DateTime<Utc> {
+        let remaining = (target - self.mean_anomaly()).rem_euclid(std::f64::consts::TAU);
+        self.epoch + Duration::milliseconds((remaining / self.mean_motion() * 1000.0) as i64)
+    }
+
+    pub fn next_perigee(&self) -> DateTime<Utc> {
+        self.next_anomaly(0.0)
+    }
+
+    pub fn next_apogee(&self) -> DateTime<Utc> {
+        self.next_anomaly(std::f64::consts::PI)
+    }
+
+    /// Perigee height above the equatorial radius (km).
+    pub fn perigee_altitude(&self) -> f64 {
+        self.semi_major_axis * (1.0 - self.eccentricity) - EQUATORIAL_RADIUS_KM
+    }
+
+    /// Apogee height above the equatorial radius (km).
+    pub fn apogee_altitude(&self) -> f64 {
+        self.semi_major_axis * (1.0 + self.eccentricity) - EQUATORIAL_RADIUS_KM
+    }
+
+    /// Whether the satellite is moving away from perigee, i.e. climbing.
+    pub fn is_ascending(&self) -> bool {
+        self.true_anomaly < 180.0
+    }
+}
@@ -0,0 +1,185 @@
+use crate::orbit::{geodetic_to_ecef, norm, Geodetic, Propagator};
+use crate::solar::{is_sunlit, sun_elevation};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How far ahead passes are predicted by default.
+pub const PASS_PREDICTION_HOURS: i64 = 48;
+/// Passes that culminate lower than this never clear the horizon clutter.
+pub const PASS_MIN_ELEVATION: f64 = 10.0;
+/// Coarse search step; shorter than any ISS pass.
+const SEARCH_STEP_SECONDS: i64 = 60;
+/// The observer counts as being in darkness once the Sun is below civil twilight.
+const DARKNESS_SUN_ELEVATION: f64 = -6.0;
+
+/// A place on the ground that passes are predicted for.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Observer {
+    pub name: String,
+    pub lat: f64,
+    pub lon: f64,
+    /// Height above the WGS84 ellipsoid (m).
+    pub elevation: f64,
+}
+
+impl Observer {
+    fn ecef(&self) -> [f64; 3] {
+        geodetic_to_ecef(&Geodetic {
+            lat: self.lat,
+            lon: self.lon,
+            alt: self.elevation / 1000.0,
+        })
+    }
+
+    /// Azimuth and elevation (degrees) of an Earth-fixed position (km).
+    pub fn look_angles(&self, target: &[f64; 3]) -> (f64, f64) {
+        let own = self.ecef();
+        let range = [target[0] - own[0], target[1] - own[1], target[2] - own[2]];
+        let (lat, lon) = (self.lat.to_radians(), self.lon.to_radians());
+
+        let east = -lon.sin() * range[0] + lon.cos() * range[1];
+        let north = -lat.sin() * lon.cos() * range[0] - lat.sin() * lon.sin() * range[1]
+            + lat.cos() * range[2];
+        let up = lat.cos() * lon.cos() * range[0] + lat.cos() * lon.sin() * range[1] + lat.sin() * range[2];
+
+        let azimuth = east.atan2(north).to_degrees().rem_euclid(360.0);
+        let elevation = (up / norm(&range)).asin().to_degrees();
+        (azimuth, elevation)
+    }
+}
+
+/// One pass of the ISS over an [`Observer`], from where it rises above the horizon to where it sets.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pass {
+    pub rise: DateTime<Utc>,
+    pub rise_azimuth: f64,
+    pub culmination: DateTime<Utc>,
+    pub culmination_azimuth: f64,
+    pub max_elevation: f64,
+    pub set: DateTime<Utc>,
+    pub set_azimuth: f64,
+    /// The ISS is sunlit while the observer is in darkness for part of the pass.
+    pub visible: bool,
+}
+
+/// Predicts the passes in the `hours` after `start` that culminate at least `min_elevation`
+/// degrees up. Rise and set are the horizon crossings.
+pub fn predict_passes(
+    propagator: &dyn Propagator,
+    observer: &Observer,
+    start: DateTime<Utc>,
+    hours: i64,
+    min_elevation: f64,
+) -> Vec<Pass> {
+    let elevation_at = |time: DateTime<Utc>| -> Option<f64> {
+        let target = propagator.ecef_at(time).ok()?;
+        Some(observer.look_angles(&target).1)
+    };
+
+    let end = start + Duration::hours(hours);
+    let step = Duration::seconds(SEARCH_STEP_SECONDS);
+    let mut passes = Vec::new();
+    let mut rise: Option<DateTime<Utc>> = None;
+    let mut previous = (start, elevation_at(start));
+
+    // A pass already in progress at `start` is reported from `start`.
+    if matches!(previous.1, Some(elevation) if elevation >= 0.0) {
+        rise = Some(start);
+    }
+
+    let mut time = start + step;
+    while time <= end {
+        let current = (time, elevation_at(time));
+        match (previous.1, current.1) {
+            (Some(before), Some(after)) if before < 0.0 && after >= 0.0 => {
+                rise = Some(bisect(&elevation_at, previous.0, time));
+            }
+            (Some(before), Some(after)) if before >= 0.0 && after < 0.0 => {
+                if let Some(rise_time) = rise.take() {
+                    let set_time = bisect(&elevation_at, previous.0, time);
+                    passes.extend(
+                        build_pass(propagator, observer, rise_time, set_time)
+                            .filter(|pass| pass.max_elevation >= min_elevation),
+                    );
+                }
+            }
+            _ => {}
+        }
+        previous = current;
+        time += step;
+    }
+
+    passes
+}
+
+/// Finds the sign change of `f` between `low` and `high` to within a second.
+fn bisect(
+    f: &impl Fn(DateTime<Utc>) -> Option<f64>,
+    mut low: DateTime<Utc>,
+    mut high: DateTime<Utc>,
+) -> DateTime<Utc> {
+    let low_sign = f(low).map_or(false, |value| value >= 0.0);
+    while (high - low).num_milliseconds() > 1000 {
+        let mid = low + (high - low) / 2;
+        if f(mid).map_or(false, |value| value >= 0.0) == low_sign {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    high
+}
+
+fn build_pass(
+    propagator: &dyn Propagator,
+    observer: &Observer,
+    rise: DateTime<Utc>,
+    set: DateTime<Utc>,
+) -> Option<Pass> {
+    let look = |time: DateTime<Utc>| -> Option<(f64, f64)> {
+        Some(observer.look_angles(&propagator.ecef_at(time).ok()?))
+    };
+
+    // Elevation is unimodal over a pass, so a ternary search finds the culmination.
+    let (mut low, mut high) = (rise, set);
+    while (high - low).num_milliseconds() > 1000 {
+        let third = (high - low) / 3;
+        let (left, right) = (low + third, high - third);
+        if look(left)?.1 < look(right)?.1 {
+            low = left;
+        } else {
+            high = right;
+        }
+    }
+    let culmination = low + (high - low) / 2;
+
+    let mut visible = false;
+    let mut time = rise;
+    while time <= set && !visible {
+        let target = propagator.ecef_at(time).ok()?;
+        visible = is_sunlit(&target, time)
+            && sun_elevation(observer.lat, observer.lon, time) < DARKNESS_SUN_ELEVATION;
+        time += Duration::seconds(30);
+    }
+
+    let (rise_azimuth, _) = look(rise)?;
+    let (culmination_azimuth, max_elevation) = look(culmination)?;
+    let (set_azimuth, _) = look(set)?;
+
+    Some(Pass {
+        rise,
+        rise_azimuth,
+        culmination,
+        culmination_azimuth,
+        max_elevation,
+        set,
+        set_azimuth,
+        visible,
+    })
+}
+
+/// Eight-point compass direction for an azimuth in degrees.
+pub fn compass_point(azimuth: f64) -> &'static str {
+    const POINTS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+    POINTS[((azimuth.rem_euclid(360.0) + 22.5) / 45.0) as usize % 8]
+}
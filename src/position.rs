@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// A sub-satellite point, as reported by a position provider or computed locally.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Position {
+    pub lat: f64,
+    pub lon: f64,
+    pub alt: f64,
+    pub timestamp: f64,
+}
+
+/// Whether a position was reported by a tracking service or computed locally.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum PositionSource {
+    Live,
+    Propagated,
+}
+
+impl fmt::Display for PositionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PositionSource::Live => write!(f, "LIVE"),
+            PositionSource::Propagated => write!(f, "PROPAGATED"),
+        }
+    }
+}
+
+impl FromStr for PositionSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "LIVE" => Ok(PositionSource::Live),
+            "PROPAGATED" => Ok(PositionSource::Propagated),
+            other => Err(format!("unknown position source '{other}'")),
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use crate::error::IssError;
+use crate::http::{HttpClient, ReqwestClient};
+use crate::iss::{get_position, json_f64};
+use crate::orbit::Propagator;
+use chrono::Utc;
+use serde::Deserialize;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub use crate::position::{Position, PositionSource};
+
+pub const WHERE_THE_ISS_URL: &str = "https://api.wheretheiss.at/v1/satellites/25544";
+pub const OPEN_NOTIFY_URL: &str = "http://api.open-notify.org/iss-now.json";
+
+/// open-notify does not report altitude, so it is filled in with the ISS mean.
+pub const DEFAULT_ALTITUDE_KM: f64 = 417.5;
+
+impl Position {
+    /// The current sub-satellite point according to `propagator`.
+    pub fn propagated(propagator: &dyn Propagator) -> Result<Position, IssError> {
+        let now = Utc::now();
+        let geodetic = propagator.geodetic_at(now)?;
+
+        Ok(Position {
+            lat: geodetic.lat,
+            lon: geodetic.lon,
+            alt: geodetic.alt,
+            timestamp: now.timestamp() as f64,
+        })
+    }
+}
+
+/// Fetches a position from `provider`, falling back to `propagator` when the provider fails.
+/// The provider's error is returned if both fail.
+pub fn fetch_with_fallback(
+    provider: &mut dyn PositionProvider,
+    propagator: Option<&dyn Propagator>,
+) -> Result<(Position, PositionSource), IssError> {
+    match provider.fetch_position() {
+        Ok(position) => Ok((position, provider.source())),
+        Err(err) => match propagator.map(Position::propagated) {
+            Some(Ok(position)) => Ok((position, PositionSource::Propagated)),
+            _ => Err(err),
+        },
+    }
+}
+
+/// A source of the current ISS position.
+pub trait PositionProvider: fmt::Debug + Send {
+    /// Short name shown in the UI.
+    fn name(&self) -> &'static str;
+
+    fn fetch_position(&mut self) -> Result<Position, IssError>;
+
+    /// Whether positions from this provider are live or computed locally.
+    fn source(&self) -> PositionSource {
+        PositionSource::Live
+    }
+
+    fn box_clone(&self) -> Box<dyn PositionProvider>;
+}
+
+impl Clone for Box<dyn PositionProvider> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+/// Position from https://wheretheiss.at.
+#[derive(Debug, Clone)]
+pub struct WhereTheIss {
+    pub url: String,
+    pub client: Arc<dyn HttpClient>,
+}
+
+impl Default for WhereTheIss {
+    fn default() -> Self {
+        WhereTheIss {
+            url: WHERE_THE_ISS_URL.to_string(),
+            client: Arc::new(ReqwestClient::default()),
+        }
+    }
+}
+
+impl PositionProvider for WhereTheIss {
+    fn name(&self) -> &'static str {
+        "wheretheiss.at"
+    }
+
+    fn fetch_position(&mut self) -> Result<Position, IssError> {
+        get_position(self.client.as_ref(), &self.url)
+    }
+
+    fn box_clone(&self) -> Box<dyn PositionProvider> {
+        Box::new(self.clone())
+    }
+}
+
+/// Position from the open-notify `iss-now.json` endpoint.
+#[derive(Debug, Clone)]
+pub struct OpenNotify {
+    pub url: String,
+    pub client: Arc<dyn HttpClient>,
+}
+
+impl Default for OpenNotify {
+    fn default() -> Self {
+        OpenNotify {
+            url: OPEN_NOTIFY_URL.to_string(),
+            client: Arc::new(ReqwestClient::default()),
+        }
+    }
+}
+
+impl PositionProvider for OpenNotify {
+    fn name(&self) -> &'static str {
+        "open-notify"
+    }
+
+    fn fetch_position(&mut self) -> Result<Position, IssError> {
+        let json = self.client.get_json(&self.url)?;
+
+        // open-notify reports the coordinates as strings
+        let coordinate = |pointer: &str| -> Result<f64, IssError> {
+            json.pointer(pointer)
+                .and_then(|value| value.as_str())
+                .and_then(|value| value.parse().ok())
+                .ok_or_else(|| IssError::MissingField(pointer.to_string()))
+        };
+
+        Ok(Position {
+            lat: coordinate("/iss_position/latitude")?,
+            lon: coordinate("/iss_position/longitude")?,
+            alt: DEFAULT_ALTITUDE_KM,
+            timestamp: json_f64(&json, "/timestamp")?,
+        })
+    }
+
+    fn box_clone(&self) -> Box<dyn PositionProvider> {
+        Box::new(self.clone())
+    }
+}
+
+/// Position computed locally by a [`Propagator`] (OEM ephemeris or TLE), no network needed.
+#[derive(Debug, Clone)]
+pub struct PropagatorProvider {
+    pub propagator: Arc<dyn Propagator>,
+}
+
+impl PropagatorProvider {
+    pub fn new(propagator: Arc<dyn Propagator>) -> Self {
+        PropagatorProvider { propagator }
+    }
+}
+
+impl PositionProvider for PropagatorProvider {
+    fn name(&self) -> &'static str {
+        self.propagator.name()
+    }
+
+    fn fetch_position(&mut self) -> Result<Position, IssError> {
+        Position::propagated(self.propagator.as_ref())
+    }
+
+    fn source(&self) -> PositionSource {
+        PositionSource::Propagated
+    }
+
+    fn box_clone(&self) -> Box<dyn PositionProvider> {
+        Box::new(self.clone())
+    }
+}
+
+/// Position sources that can be picked at runtime with `--provider`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum ProviderKind {
+    WhereTheIss,
+    OpenNotify,
+    Oem,
+    Tle,
+}
+
+impl FromStr for ProviderKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "wheretheiss" | "wheretheiss.at" => Ok(ProviderKind::WhereTheIss),
+            "open-notify" | "opennotify" => Ok(ProviderKind::OpenNotify),
+            "oem" => Ok(ProviderKind::Oem),
+            "tle" | "sgp4" => Ok(ProviderKind::Tle),
+            other => Err(format!(
+                "unknown provider '{other}', expected one of: wheretheiss, open-notify, oem, tle"
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for ProviderKind {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
@@ -0,0 +1,246 @@
+use crate::error::IssError;
+use crate::iss::Iss;
+use crate::provider::{Position, PositionSource};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::time::Instant;
+
+/// Recorded time that a replay skips over instead of waiting through, e.g. between sessions.
+const MAX_REPLAY_GAP_SECONDS: i64 = 300;
+
+/// One recorded position of the ISS.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sample {
+    pub time: DateTime<Utc>,
+    pub lat: f64,
+    pub lon: f64,
+    pub alt: f64,
+    pub country: String,
+    pub source: PositionSource,
+}
+
+impl Sample {
+    pub const CSV_HEADER: &'static str = "time,lat,lon,alt,country,source";
+
+    /// The current position of `iss`.
+    pub fn from_iss(iss: &Iss) -> Self {
+        Sample {
+            time: DateTime::from_timestamp_millis((iss.time * 1000.0) as i64).unwrap_or_else(Utc::now),
+            lat: iss.lat,
+            lon: iss.lon,
+            alt: iss.alt,
+            country: iss.country.clone(),
+            source: iss.source,
+        }
+    }
+
+    pub fn position(&self) -> Position {
+        Position {
+            lat: self.lat,
+            lon: self.lon,
+            alt: self.alt,
+            timestamp: self.time.timestamp_millis() as f64 / 1000.0,
+        }
+    }
+
+    pub fn csv_row(&self) -> String {
+        format!(
+            "{},{},{},{},{},{}",
+            self.time.to_rfc3339(),
+            self.lat,
+            self.lon,
+            self.alt,
+            csv_field(&self.country),
+            self.source
+        )
+    }
+
+    fn from_csv_row(line: &str) -> Result<Self, String> {
+        let fields = split_csv_row(line);
+        if fields.len() < 6 {
+            return Err(format!("expected 6 fields, found {}", fields.len()));
+        }
+        let number = |index: usize| {
+            fields[index]
+                .parse::<f64>()
+                .map_err(|err| format!("{}: {err}", fields[index]))
+        };
+
+        Ok(Sample {
+            time: DateTime::parse_from_rfc3339(&fields[0])
+                .map_err(|err| format!("{}: {err}", fields[0]))?
+                .with_timezone(&Utc),
+            lat: number(1)?,
+            lon: number(2)?,
+            alt: number(3)?,
+            country: fields[4].clone(),
+            source: fields[5].parse()?,
+        })
+    }
+}
+
+/// On-disk layouts of a recording, picked from the file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// One JSON object per line.
+    JsonLines,
+    Csv,
+}
+
+impl LogFormat {
+    /// CSV for `.csv` files, JSON Lines for anything else.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("csv") => LogFormat::Csv,
+            _ => LogFormat::JsonLines,
+        }
+    }
+}
+
+/// Appends samples to a recording, one line each, flushed as they are written.
+#[derive(Debug)]
+pub struct Recorder {
+    file: File,
+    format: LogFormat,
+}
+
+impl Recorder {
+    /// Opens `path` for appending, creating it (with a CSV header if needed) when missing.
+    pub fn open(path: &Path) -> Result<Self, IssError> {
+        let format = LogFormat::from_path(path);
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        if format == LogFormat::Csv && file.metadata()?.len() == 0 {
+            writeln!(file, "{}", Sample::CSV_HEADER)?;
+        }
+
+        Ok(Recorder { file, format })
+    }
+
+    pub fn record(&mut self, sample: &Sample) -> Result<(), IssError> {
+        let line = match self.format {
+            LogFormat::JsonLines => serde_json::to_string(sample)?,
+            LogFormat::Csv => sample.csv_row(),
+        };
+        writeln!(self.file, "{line}")?;
+        Ok(())
+    }
+}
+
+/// Reads every sample of a recording, sorted by time.
+pub fn read_samples(path: &Path) -> Result<Vec<Sample>, IssError> {
+    let format = LogFormat::from_path(path);
+    let reader = BufReader::new(File::open(path)?);
+
+    let mut samples = Vec::new();
+    for (number, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() || (format == LogFormat::Csv && line.starts_with("time,")) {
+            continue;
+        }
+        let sample = match format {
+            LogFormat::JsonLines => serde_json::from_str(&line).map_err(|err| err.to_string()),
+            LogFormat::Csv => Sample::from_csv_row(&line),
+        }
+        .map_err(|msg| IssError::InvalidRecord(format!("{} line {}: {msg}", path.display(), number + 1)))?;
+        samples.push(sample);
+    }
+    samples.sort_by_key(|sample| sample.time);
+
+    Ok(samples)
+}
+
+/// Plays a recording back against the wall clock, `speed` times faster than it was recorded.
+#[derive(Debug, Clone)]
+pub struct Replay {
+    samples: Vec<Sample>,
+    next: usize,
+    /// How far into the recording playback has reached.
+    clock: DateTime<Utc>,
+    last_tick: Instant,
+    pub speed: f64,
+}
+
+impl Replay {
+    pub fn new(samples: Vec<Sample>, speed: f64) -> Result<Self, IssError> {
+        // A speed of 0 would never move, and a negative one would run the clock backwards.
+        if !(speed > 0.0 && speed.is_finite()) {
+            return Err(IssError::InvalidConfig(format!(
+                "the replay speed must be a number greater than 0, not {speed}"
+            )));
+        }
+        let clock = samples
+            .first()
+            .ok_or_else(|| IssError::InvalidRecord(String::from("the recording has no samples")))?
+            .time;
+
+        Ok(Replay {
+            samples,
+            next: 0,
+            clock,
+            last_tick: Instant::now(),
+            speed,
+        })
+    }
+
+    /// Samples that have come due since the last call, oldest first.
+    pub fn advance(&mut self) -> &[Sample] {
+        let elapsed = self.last_tick.elapsed().as_secs_f64() * self.speed;
+        self.last_tick = Instant::now();
+        self.clock += Duration::milliseconds((elapsed * 1000.0) as i64);
+
+        if let Some(upcoming) = self.samples.get(self.next) {
+            if (upcoming.time - self.clock).num_seconds() > MAX_REPLAY_GAP_SECONDS {
+                self.clock = upcoming.time;
+            }
+        }
+
+        let start = self.next;
+        while self.next < self.samples.len() && self.samples[self.next].time <= self.clock {
+            self.next += 1;
+        }
+        &self.samples[start..self.next]
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.samples.len()
+    }
+
+    pub fn clock(&self) -> DateTime<Utc> {
+        self.clock
+    }
+}
+
+/// Quotes a CSV field when it contains a separator, quote or line break.
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Splits one CSV row written with [`csv_field`] back into its fields.
+fn split_csv_row(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', _) => quoted = !quoted,
+            (',', false) => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields
+}
@@ -0,0 +1,179 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// Simplified country, sea and ocean outlines, compiled into the crate so the lookup works offline and
+/// in the browser. See the header of the file for its format.
+const REGIONS: &str = include_str!("regions.txt");
+
+/// What a [`Region`] covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegionKind {
+    Country,
+    Sea,
+    Ocean,
+}
+
+impl fmt::Display for RegionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegionKind::Country => write!(f, "country"),
+            RegionKind::Sea => write!(f, "sea"),
+            RegionKind::Ocean => write!(f, "ocean"),
+        }
+    }
+}
+
+impl FromStr for RegionKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "country" => Ok(RegionKind::Country),
+            "sea" => Ok(RegionKind::Sea),
+            "ocean" => Ok(RegionKind::Ocean),
+            other => Err(format!("unknown region kind '{other}'")),
+        }
+    }
+}
+
+/// One polygon of a country, sea or ocean. Countries with islands have one region per polygon.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Region {
+    pub name: String,
+    pub kind: RegionKind,
+    /// `(lon, lat)` vertices in degrees, without repeating the first one.
+    pub outline: Vec<(f64, f64)>,
+    /// `(min_lon, min_lat, max_lon, max_lat)`, to skip most regions without the full test.
+    bounds: (f64, f64, f64, f64),
+}
+
+impl Region {
+    pub fn new(name: &str, kind: RegionKind, outline: Vec<(f64, f64)>) -> Region {
+        let bounds = outline.iter().fold(
+            (
+                f64::INFINITY,
+                f64::INFINITY,
+                f64::NEG_INFINITY,
+                f64::NEG_INFINITY,
+            ),
+            |(min_lon, min_lat, max_lon, max_lat), &(lon, lat)| {
+                (
+                    min_lon.min(lon),
+                    min_lat.min(lat),
+                    max_lon.max(lon),
+                    max_lat.max(lat),
+                )
+            },
+        );
+        Region {
+            name: String::from(name),
+            kind,
+            outline,
+            bounds,
+        }
+    }
+
+    /// Whether the point is inside the outline, by counting the edges a ray to the east crosses.
+    /// Longitudes are taken as they are, so an outline must not cross the antimeridian.
+    pub fn contains(&self, lat: f64, lon: f64) -> bool {
+        let (min_lon, min_lat, max_lon, max_lat) = self.bounds;
+        if lon < min_lon || lon > max_lon || lat < min_lat || lat > max_lat {
+            return false;
+        }
+
+        let mut inside = false;
+        let mut previous = self.outline[self.outline.len() - 1];
+        for &vertex in &self.outline {
+            let ((lon1, lat1), (lon2, lat2)) = (previous, vertex);
+            if (lat1 > lat) != (lat2 > lat)
+                && lon < lon1 + (lat - lat1) / (lat2 - lat1) * (lon2 - lon1)
+            {
+                inside = !inside;
+            }
+            previous = vertex;
+        }
+        inside
+    }
+}
+
+/// A region whose vertices are still being read: its name, kind and outline so far.
+type Partial = (String, RegionKind, Vec<(f64, f64)>);
+
+/// Parses the format of `regions.txt`: a `<kind> <name>` line starts a region, and the indented
+/// lines after it hold its `lon lat` vertices, separated by `;`. `#` starts a comment.
+pub fn parse_regions(text: &str) -> Result<Vec<Region>, String> {
+    let mut regions = Vec::new();
+    let mut current: Option<Partial> = None;
+
+    for (index, line) in text.lines().enumerate() {
+        let number = index + 1;
+        let content = line.split('#').next().unwrap_or_default();
+        if content.trim().is_empty() {
+            continue;
+        }
+
+        if !content.starts_with(char::is_whitespace) {
+            regions.extend(current.take().map(finish).transpose()?);
+            let (kind, name) = content
+                .trim()
+                .split_once(' ')
+                .ok_or_else(|| format!("line {number}: expected '<kind> <name>'"))?;
+            current = Some((
+                String::from(name.trim()),
+                kind.parse().map_err(|e| format!("line {number}: {e}"))?,
+                Vec::new(),
+            ));
+            continue;
+        }
+
+        let (_, _, outline) = current
+            .as_mut()
+            .ok_or_else(|| format!("line {number}: vertices before any region"))?;
+        for pair in content.split(';').filter(|pair| !pair.trim().is_empty()) {
+            let mut numbers = pair.split_whitespace().map(str::parse::<f64>);
+            match (numbers.next(), numbers.next(), numbers.next()) {
+                (Some(Ok(lon)), Some(Ok(lat)), None)
+                    if (-180.0..=180.0).contains(&lon) && (-90.0..=90.0).contains(&lat) =>
+                {
+                    outline.push((lon, lat))
+                }
+                _ => {
+                    return Err(format!(
+                        "line {number}: '{}' is not a 'lon lat' pair",
+                        pair.trim()
+                    ))
+                }
+            }
+        }
+    }
+    regions.extend(current.take().map(finish).transpose()?);
+    Ok(regions)
+}
+
+fn finish((name, kind, outline): Partial) -> Result<Region, String> {
+    if outline.len() < 3 {
+        return Err(format!("{name} has fewer than 3 vertices"));
+    }
+    Ok(Region::new(&name, kind, outline))
+}
+
+/// Every embedded region, in file order: the oceans, the seas, then the countries.
+pub fn regions() -> &'static [Region] {
+    static PARSED: OnceLock<Vec<Region>> = OnceLock::new();
+    PARSED.get_or_init(|| parse_regions(REGIONS).expect("the embedded regions.txt parses"))
+}
+
+/// The country under the point, or else the sea, or else the ocean. `None` only for the lakes no
+/// outline covers.
+pub fn locate(lat: f64, lon: f64) -> Option<&'static Region> {
+    let lon = (lon + 180.0).rem_euclid(360.0) - 180.0;
+    let regions = regions();
+    [RegionKind::Country, RegionKind::Sea, RegionKind::Ocean]
+        .into_iter()
+        .find_map(|kind| {
+            regions
+                .iter()
+                .find(|region| region.kind == kind && region.contains(lat, lon))
+        })
+}
@@ -0,0 +1,71 @@
+use crate::error::IssError;
+use crate::iss::{get_country, MAX_POS_DATA};
+use crate::orbit::{OrbitalElements, Propagator};
+use crate::provider::Position;
+use crate::tle::{Sgp4, Tle};
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+/// NORAD catalogue number of the ISS, which is always tracked.
+pub const ISS_NORAD_ID: u32 = 25544;
+
+/// A satellite tracked alongside the ISS. Its position is propagated locally from its
+/// elements, so following it needs no network after the TLE has been downloaded.
+#[derive(Debug, Clone)]
+pub struct Satellite {
+    pub norad_id: u32,
+    pub name: String,
+    pub propagator: Arc<dyn Propagator>,
+    /// The latest position, once [`Satellite::update`] has been called.
+    pub position: Option<Position>,
+    pub country: String,
+    /// Positions at every update, oldest first, at most [`MAX_POS_DATA`].
+    pub history: Vec<Position>,
+    pub elements: Option<OrbitalElements>,
+}
+
+impl Satellite {
+    pub fn new(norad_id: u32, name: String, propagator: Arc<dyn Propagator>) -> Self {
+        Satellite {
+            norad_id,
+            name,
+            propagator,
+            position: None,
+            country: String::new(),
+            history: Vec::new(),
+            elements: None,
+        }
+    }
+
+    /// Tracks the satellite `tle` describes, named `name` or else after the TLE's name line.
+    pub fn from_tle(tle: Tle, name: Option<String>) -> Result<Self, IssError> {
+        let name = name
+            .or_else(|| tle.name.clone())
+            .unwrap_or_else(|| format!("NORAD {}", tle.norad_id));
+        Ok(Satellite::new(tle.norad_id, name, Arc::new(Sgp4::new(tle)?)))
+    }
+
+    /// Moves the satellite to where it is at `time`.
+    pub fn update(&mut self, time: DateTime<Utc>) -> Result<(), IssError> {
+        let geodetic = self.propagator.geodetic_at(time)?;
+        let position = Position {
+            lat: geodetic.lat,
+            lon: geodetic.lon,
+            alt: geodetic.alt,
+            timestamp: time.timestamp_millis() as f64 / 1000.0,
+        };
+
+        self.country = get_country(position.lat, position.lon).unwrap_or_else(|_| "Unknown Country".to_string());
+        self.elements = self
+            .propagator
+            .state_at(time)
+            .ok()
+            .map(|state| OrbitalElements::from_state(&state));
+        self.position = Some(position);
+        self.history.push(position);
+        if self.history.len() > MAX_POS_DATA {
+            self.history.drain(..self.history.len() - MAX_POS_DATA);
+        }
+        Ok(())
+    }
+}
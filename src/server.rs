@@ -0,0 +1,198 @@
+use crate::error::IssError;
+use crate::iss::{nearest_place, Iss, NearestPlace};
+use crate::orbit::{geodetic_to_ecef, Geodetic};
+use crate::passes::{Observer, Pass};
+use crate::recorder::Sample;
+use crate::solar::is_sunlit;
+use crate::ui::App;
+use crate::weather::Weather;
+use serde::Serialize;
+use serde_json::json;
+use std::io;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// Paths answered by [`respond`].
+pub const ENDPOINTS: [&str; 6] = ["/position", "/history", "/crew", "/weather", "/passes", "/groundtrack"];
+
+/// The answer to one request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reply {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl Reply {
+    fn json(value: &impl Serialize) -> Reply {
+        match serde_json::to_string(value) {
+            Ok(body) => Reply {
+                status: 200,
+                content_type: "application/json",
+                body,
+            },
+            Err(err) => Reply::error(500, &err.to_string()),
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Reply {
+        Reply {
+            status,
+            content_type: "application/json",
+            body: json!({ "error": message }).to_string(),
+        }
+    }
+}
+
+/// The latest position, as printed by `now --format json`, with what the Tracker screen adds.
+#[derive(Debug, Serialize)]
+struct PositionReply<'a> {
+    #[serde(flatten)]
+    sample: Sample,
+    provider: &'static str,
+    alt_perigee_apogee: &'a str,
+    cross_check_km: Option<f64>,
+    sunlit: bool,
+    nearest_place: Option<NearestPlace>,
+}
+
+/// The weather below the ISS, as printed by `weather --format json`.
+#[derive(Debug, Serialize)]
+struct WeatherReply<'a> {
+    lat: f64,
+    lon: f64,
+    description: String,
+    #[serde(flatten)]
+    weather: &'a Weather,
+}
+
+/// The upcoming passes, as printed by `passes --format json`.
+#[derive(Debug, Serialize)]
+struct PassesReply<'a> {
+    observer: &'a Observer,
+    passes: &'a [Pass],
+}
+
+/// The answer to a GET of `url` from the tracker's current state. The query string is ignored.
+pub fn respond(url: &str, app: &App, iss: &Iss) -> Reply {
+    let path = url.split('?').next().unwrap_or_default();
+    match path.trim_end_matches('/') {
+        "" => Reply::json(&json!({ "endpoints": ENDPOINTS })),
+        "/position" if iss.pos_data.is_empty() => Reply::error(503, "no position has arrived yet"),
+        "/position" => {
+            let sample = Sample::from_iss(iss);
+            let place = nearest_place(sample.lat, sample.lon);
+            let sunlit = is_sunlit(
+                &geodetic_to_ecef(&Geodetic {
+                    lat: sample.lat,
+                    lon: sample.lon,
+                    alt: sample.alt,
+                }),
+                sample.time,
+            );
+            Reply::json(&PositionReply {
+                sample,
+                provider: iss.provider.name(),
+                alt_perigee_apogee: &iss.alt_perigee_apogee,
+                cross_check_km: iss.cross_check_km,
+                sunlit,
+                nearest_place: place,
+            })
+        }
+        "/history" => Reply::json(&iss.pos_data),
+        "/crew" if iss.crew.is_empty() => Reply::error(503, "the crew has not arrived yet"),
+        "/crew" => Reply::json(&iss.crew.lines().collect::<Vec<_>>()),
+        "/weather" => match &iss.weather {
+            Some(weather) => Reply::json(&WeatherReply {
+                lat: iss.lat,
+                lon: iss.lon,
+                description: weather.description(),
+                weather,
+            }),
+            None => Reply::error(503, "the weather has not arrived yet"),
+        },
+        "/passes" => match &app.observer {
+            Some(observer) => Reply::json(&PassesReply {
+                observer,
+                passes: &app.passes,
+            }),
+            None => Reply::error(404, "no observer is set; start with --lat and --lon"),
+        },
+        "/groundtrack" => Reply {
+            status: 200,
+            content_type: "application/geo+json",
+            body: app.tracks(iss).to_geojson(),
+        },
+        _ => Reply::error(404, &format!("unknown endpoint; try one of {}", ENDPOINTS.join(", "))),
+    }
+}
+
+/// Serves the tracker's state over HTTP, to any origin, for dashboards and `rsiss.html`.
+pub struct ApiServer {
+    server: Server,
+}
+
+impl ApiServer {
+    /// Listens on `addr`, e.g. `127.0.0.1:8080`; port 0 picks a free one.
+    pub fn bind(addr: &str) -> Result<ApiServer, IssError> {
+        let server = Server::http(addr).map_err(io::Error::other)?;
+        Ok(ApiServer { server })
+    }
+
+    /// The address actually listened on.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.server.server_addr().to_ip()
+    }
+
+    /// Answers the next request, if one arrives within `timeout`. A request that cannot be
+    /// answered, e.g. because the client hung up, is logged to stderr; only a failure of the
+    /// server itself is returned.
+    pub fn answer_next(&self, app: &App, iss: &Iss, timeout: Duration) -> Result<(), IssError> {
+        if let Some(request) = self.server.recv_timeout(timeout)? {
+            let url = request.url().to_string();
+            if let Err(err) = answer(request, app, iss) {
+                eprintln!("{url}: {err}");
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies the worker's updates, and moves the replay on when there is one, between
+    /// requests until the server fails.
+    pub fn run(&self, app: &mut App, iss: &mut Iss) -> Result<(), IssError> {
+        let mut refreshed = Instant::now();
+        loop {
+            app.poll_worker(iss);
+            if app.replay.is_some() && refreshed.elapsed() >= app.config.intervals.refresh() {
+                app.update_position(iss);
+                refreshed = Instant::now();
+            }
+            self.answer_next(app, iss, app.config.intervals.poll())?;
+        }
+    }
+}
+
+fn answer(request: Request, app: &App, iss: &Iss) -> Result<(), IssError> {
+    let reply = match request.method() {
+        Method::Get | Method::Head => respond(request.url(), app, iss),
+        // CORS preflight.
+        Method::Options => Reply {
+            status: 204,
+            content_type: "text/plain",
+            body: String::new(),
+        },
+        _ => Reply::error(405, "only GET is supported"),
+    };
+
+    let response = Response::from_string(reply.body)
+        .with_status_code(reply.status)
+        .with_header(header("Content-Type", reply.content_type))
+        .with_header(header("Access-Control-Allow-Origin", "*"))
+        .with_header(header("Access-Control-Allow-Methods", "GET, OPTIONS"));
+    Ok(request.respond(response)?)
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name, value).expect("header names and values are ASCII")
+}
@@ -0,0 +1,91 @@
+use crate::orbit::{gmst, norm};
+use chrono::{DateTime, Utc};
+
+/// Equatorial radius used for the Earth's shadow cylinder (km).
+const SHADOW_RADIUS_KM: f64 = 6378.137;
+/// Longitude step between points of the terminator (degrees).
+const TERMINATOR_STEP_DEGREES: f64 = 2.0;
+/// Solar declination (radians) below which the terminator is drawn as the equinox meridians.
+const EQUINOX_DECLINATION: f64 = 1e-9;
+
+/// Unit vector from the Earth's centre towards the Sun in the Earth-fixed frame.
+/// Uses the low-precision solar coordinates from the Astronomical Almanac (~0.01°).
+pub fn sun_direction_ecef(time: DateTime<Utc>) -> [f64; 3] {
+    let days = time.timestamp_millis() as f64 / 86_400_000.0 + 2_440_587.5 - 2_451_545.0;
+    let mean_longitude = 280.460 + 0.985_647_4 * days;
+    let mean_anomaly = (357.528 + 0.985_600_3 * days).to_radians();
+    let ecliptic_longitude = (mean_longitude
+        + 1.915 * mean_anomaly.sin()
+        + 0.020 * (2.0 * mean_anomaly).sin())
+    .to_radians();
+    let obliquity = (23.439 - 0.000_000_4 * days).to_radians();
+
+    let x = ecliptic_longitude.cos();
+    let y = obliquity.cos() * ecliptic_longitude.sin();
+    let z = obliquity.sin() * ecliptic_longitude.sin();
+
+    let theta = gmst(time);
+    [
+        x * theta.cos() + y * theta.sin(),
+        -x * theta.sin() + y * theta.cos(),
+        z,
+    ]
+}
+
+/// Latitude and longitude (degrees) of the point where the Sun is overhead.
+pub fn subsolar_point(time: DateTime<Utc>) -> (f64, f64) {
+    let sun = sun_direction_ecef(time);
+    (sun[2].asin().to_degrees(), sun[1].atan2(sun[0]).to_degrees())
+}
+
+/// The day/night boundary as (lon, lat) points from -180° to 180°: where the centre of the
+/// Sun is on the horizon. At an equinox it is the meridians 90° either side of the Sun, drawn
+/// up the western one and down the eastern one.
+pub fn terminator(time: DateTime<Utc>) -> Vec<(f64, f64)> {
+    let (sun_lat, sun_lon) = subsolar_point(time);
+    let declination = sun_lat.to_radians();
+    if declination.abs() < EQUINOX_DECLINATION {
+        let wrap = |lon: f64| (lon + 180.0).rem_euclid(360.0) - 180.0;
+        let (west, east) = match (wrap(sun_lon - 90.0), wrap(sun_lon + 90.0)) {
+            (a, b) if a < b => (a, b),
+            (a, b) => (b, a),
+        };
+        return vec![(west, -90.0), (west, 90.0), (east, 90.0), (east, -90.0)];
+    }
+    let steps = (360.0 / TERMINATOR_STEP_DEGREES) as usize;
+
+    (0..=steps)
+        .map(|step| {
+            let lon = -180.0 + step as f64 * TERMINATOR_STEP_DEGREES;
+            // sin φ sin δ + cos φ cos δ cos H = 0, so tan φ = -cos H / tan δ.
+            let hour_angle = (lon - sun_lon).to_radians();
+            let lat = (-hour_angle.cos() / declination.tan()).atan();
+            (lon, lat.to_degrees())
+        })
+        .collect()
+}
+
+/// Whether an Earth-fixed position (km) is in sunlight, using a cylindrical shadow model.
+pub fn is_sunlit(position: &[f64; 3], time: DateTime<Utc>) -> bool {
+    let sun = sun_direction_ecef(time);
+    let along = position[0] * sun[0] + position[1] * sun[1] + position[2] * sun[2];
+    if along >= 0.0 {
+        return true;
+    }
+
+    let perpendicular = [
+        position[0] - along * sun[0],
+        position[1] - along * sun[1],
+        position[2] - along * sun[2],
+    ];
+    norm(&perpendicular) > SHADOW_RADIUS_KM
+}
+
+/// Elevation of the Sun (degrees) seen from latitude/longitude in degrees.
+pub fn sun_elevation(lat: f64, lon: f64, time: DateTime<Utc>) -> f64 {
+    let sun = sun_direction_ecef(time);
+    let (lat, lon) = (lat.to_radians(), lon.to_radians());
+    let up = [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()];
+
+    (up[0] * sun[0] + up[1] * sun[1] + up[2] * sun[2]).asin().to_degrees()
+}
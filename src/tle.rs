@@ -0,0 +1,482 @@
+use crate::error::IssError;
+use crate::http::HttpClient;
+use crate::orbit::{Propagator, StateVector};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use std::f64::consts::TAU;
+use std::path::Path;
+
+pub const CELESTRAK_TLE_URL: &str = "https://celestrak.org/NORAD/elements/gp.php?CATNR=25544&FORMAT=TLE";
+/// CelesTrak's general perturbations query, for satellites other than the ISS.
+pub const CELESTRAK_GP_URL: &str = "https://celestrak.org/NORAD/elements/gp.php";
+
+// WGS-72 constants, as used by the SGP4 reference implementation.
+const EARTH_RADIUS_KM: f64 = 6378.135;
+const XKE: f64 = 0.074_366_916_133_17;
+const J2: f64 = 0.001_082_616;
+const J3: f64 = -0.000_002_538_81;
+const J4: f64 = -0.000_001_655_97;
+const J3OJ2: f64 = J3 / J2;
+const MINUTES_PER_DAY: f64 = 1440.0;
+
+/// A two-line element set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tle {
+    pub name: Option<String>,
+    pub norad_id: u32,
+    pub epoch: DateTime<Utc>,
+    /// First derivative of mean motion / 2 (rev/day²).
+    pub mean_motion_dot: f64,
+    /// Drag term (1/earth radii).
+    pub bstar: f64,
+    pub inclination: f64,
+    pub raan: f64,
+    pub eccentricity: f64,
+    pub arg_perigee: f64,
+    pub mean_anomaly: f64,
+    /// Mean motion (rev/day).
+    pub mean_motion: f64,
+}
+
+impl Tle {
+    /// Parses a TLE with or without a leading name line.
+    pub fn parse(text: &str) -> Result<Self, IssError> {
+        let lines: Vec<&str> = text.lines().map(str::trim_end).filter(|line| !line.is_empty()).collect();
+        let first = lines
+            .iter()
+            .position(|line| line.starts_with("1 "))
+            .ok_or_else(|| invalid("line 1 not found"))?;
+        let line1 = lines[first];
+        let line2 = *lines.get(first + 1).ok_or_else(|| invalid("line 2 not found"))?;
+        if !line2.starts_with("2 ") {
+            return Err(invalid("line 2 must follow line 1"));
+        }
+        // Columns are sliced by byte below.
+        if !line1.is_ascii() || !line2.is_ascii() {
+            return Err(invalid("lines must be ASCII"));
+        }
+        if line1.len() < 69 || line2.len() < 69 {
+            return Err(invalid("lines must be 69 characters long"));
+        }
+        for line in [line1, line2] {
+            if !checksum_ok(line) {
+                return Err(invalid("checksum mismatch"));
+            }
+        }
+        let name = match first {
+            0 => None,
+            _ => Some(lines[first - 1].trim_start_matches("0 ").trim().to_string()),
+        };
+
+        let epoch_year: i32 = field(line1, 18, 20)?;
+        let epoch_day: f64 = field(line1, 20, 32)?;
+        let year = if epoch_year < 57 { 2000 + epoch_year } else { 1900 + epoch_year };
+        let new_year = NaiveDate::from_ymd_opt(year, 1, 1)
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .ok_or_else(|| invalid("bad epoch year"))?
+            .and_utc();
+        let epoch = new_year + Duration::microseconds(((epoch_day - 1.0) * 86_400e6).round() as i64);
+
+        Ok(Tle {
+            name,
+            norad_id: field(line1, 2, 7)?,
+            epoch,
+            mean_motion_dot: field(line1, 33, 43)?,
+            bstar: implied_decimal(&line1[53..61])?,
+            inclination: field(line2, 8, 16)?,
+            raan: field(line2, 17, 25)?,
+            eccentricity: format!("0.{}", line2[26..33].trim())
+                .parse()
+                .map_err(|_| invalid("bad eccentricity"))?,
+            arg_perigee: field(line2, 34, 42)?,
+            mean_anomaly: field(line2, 43, 51)?,
+            mean_motion: field(line2, 52, 63)?,
+        })
+    }
+
+    /// Loads a TLE from a local file.
+    pub fn from_file(path: &Path) -> Result<Self, IssError> {
+        Tle::parse(&std::fs::read_to_string(path)?)
+    }
+}
+
+/// Downloads the current elements from `url` (CelesTrak by default).
+pub fn fetch_tle(client: &dyn HttpClient, url: &str) -> Result<Tle, IssError> {
+    Tle::parse(&client.get(url)?)
+}
+
+/// Where `gp_url` serves the elements of `norad_id` as a TLE.
+pub fn celestrak_tle_url(gp_url: &str, norad_id: u32) -> String {
+    format!("{gp_url}?CATNR={norad_id}&FORMAT=TLE")
+}
+
+fn invalid(msg: &str) -> IssError {
+    IssError::InvalidTle(msg.to_string())
+}
+
+fn field<T: std::str::FromStr>(line: &str, start: usize, end: usize) -> Result<T, IssError> {
+    line.get(start..end)
+        .and_then(|text| text.trim().parse().ok())
+        .ok_or_else(|| invalid(&format!("bad field in columns {}-{}", start + 1, end)))
+}
+
+/// Parses the `±NNNNN±E` notation with an implied leading decimal point.
+fn implied_decimal(text: &str) -> Result<f64, IssError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(0.0);
+    }
+    let (mantissa, exponent) = text.split_at(text.len() - 2);
+    let (sign, digits) = match mantissa.strip_prefix('-') {
+        Some(digits) => (-1.0, digits),
+        None => (1.0, mantissa.trim_start_matches('+')),
+    };
+    let mantissa: f64 = format!("0.{digits}").parse().map_err(|_| invalid("bad drag term"))?;
+    let exponent: i32 = exponent.parse().map_err(|_| invalid("bad drag term exponent"))?;
+
+    Ok(sign * mantissa * 10f64.powi(exponent))
+}
+
+fn checksum_ok(line: &str) -> bool {
+    let sum: u32 = line[..68]
+        .chars()
+        .map(|c| match c {
+            '-' => 1,
+            _ => c.to_digit(10).unwrap_or(0),
+        })
+        .sum();
+    line[68..69].parse::<u32>().map_or(false, |expected| sum % 10 == expected)
+}
+
+/// Near-Earth SGP4 propagator (Hoots & Roehrich, as revised by Vallado et al. 2006).
+/// Deep-space orbits (period of 225 minutes or more) are rejected.
+#[derive(Debug, Clone)]
+pub struct Sgp4 {
+    pub tle: Tle,
+    is_simple: bool,
+    ecco: f64,
+    inclo: f64,
+    nodeo: f64,
+    argpo: f64,
+    mo: f64,
+    no: f64,
+    bstar: f64,
+    aycof: f64,
+    xlcof: f64,
+    con41: f64,
+    x1mth2: f64,
+    x7thm1: f64,
+    cc1: f64,
+    cc4: f64,
+    cc5: f64,
+    d2: f64,
+    d3: f64,
+    d4: f64,
+    delmo: f64,
+    eta: f64,
+    argpdot: f64,
+    mdot: f64,
+    nodedot: f64,
+    nodecf: f64,
+    omgcof: f64,
+    xmcof: f64,
+    sinmao: f64,
+    t2cof: f64,
+    t3cof: f64,
+    t4cof: f64,
+    t5cof: f64,
+}
+
+impl Sgp4 {
+    pub fn new(tle: Tle) -> Result<Self, IssError> {
+        let ecco = tle.eccentricity;
+        let inclo = tle.inclination.to_radians();
+        let nodeo = tle.raan.to_radians();
+        let argpo = tle.arg_perigee.to_radians();
+        let mo = tle.mean_anomaly.to_radians();
+        let no_kozai = tle.mean_motion * TAU / MINUTES_PER_DAY;
+        let bstar = tle.bstar;
+
+        if TAU / no_kozai >= 225.0 {
+            return Err(IssError::Propagation(String::from(
+                "deep-space orbits are not supported",
+            )));
+        }
+
+        // Recover the original mean motion and semi-major axis from the Kozai elements.
+        let eccsq = ecco * ecco;
+        let omeosq = 1.0 - eccsq;
+        let rteosq = omeosq.sqrt();
+        let cosio = inclo.cos();
+        let cosio2 = cosio * cosio;
+        let ak = (XKE / no_kozai).powf(2.0 / 3.0);
+        let d1 = 0.75 * J2 * (3.0 * cosio2 - 1.0) / (rteosq * omeosq);
+        let del = d1 / (ak * ak);
+        let adel = ak * (1.0 - del * del - del * (1.0 / 3.0 + 134.0 * del * del / 81.0));
+        let del = d1 / (adel * adel);
+        let no = no_kozai / (1.0 + del);
+
+        let ao = (XKE / no).powf(2.0 / 3.0);
+        let sinio = inclo.sin();
+        let po = ao * omeosq;
+        let con42 = 1.0 - 5.0 * cosio2;
+        let con41 = -con42 - cosio2 - cosio2;
+        let posq = po * po;
+        let rp = ao * (1.0 - ecco);
+
+        let is_simple = rp < 220.0 / EARTH_RADIUS_KM + 1.0;
+
+        // Atmospheric density parameters, adjusted for low perigees.
+        let mut sfour = 78.0 / EARTH_RADIUS_KM + 1.0;
+        let mut qzms24 = ((120.0 - 78.0) / EARTH_RADIUS_KM).powi(4);
+        let perigee = (rp - 1.0) * EARTH_RADIUS_KM;
+        if perigee < 156.0 {
+            sfour = if perigee < 98.0 { 20.0 } else { perigee - 78.0 };
+            qzms24 = ((120.0 - sfour) / EARTH_RADIUS_KM).powi(4);
+            sfour = sfour / EARTH_RADIUS_KM + 1.0;
+        }
+
+        let pinvsq = 1.0 / posq;
+        let tsi = 1.0 / (ao - sfour);
+        let eta = ao * ecco * tsi;
+        let etasq = eta * eta;
+        let eeta = ecco * eta;
+        let psisq = (1.0 - etasq).abs();
+        let coef = qzms24 * tsi.powi(4);
+        let coef1 = coef / psisq.powf(3.5);
+        let cc2 = coef1
+            * no
+            * (ao * (1.0 + 1.5 * etasq + eeta * (4.0 + etasq))
+                + 0.375 * J2 * tsi / psisq * con41 * (8.0 + 3.0 * etasq * (8.0 + etasq)));
+        let cc1 = bstar * cc2;
+        let cc3 = if ecco > 1.0e-4 {
+            -2.0 * coef * tsi * J3OJ2 * no * sinio / ecco
+        } else {
+            0.0
+        };
+        let x1mth2 = 1.0 - cosio2;
+        let cc4 = 2.0
+            * no
+            * coef1
+            * ao
+            * omeosq
+            * (eta * (2.0 + 0.5 * etasq) + ecco * (0.5 + 2.0 * etasq)
+                - J2 * tsi / (ao * psisq)
+                    * (-3.0 * con41 * (1.0 - 2.0 * eeta + etasq * (1.5 - 0.5 * eeta))
+                        + 0.75 * x1mth2 * (2.0 * etasq - eeta * (1.0 + etasq)) * (2.0 * argpo).cos()));
+        let cc5 = 2.0 * coef1 * ao * omeosq * (1.0 + 2.75 * (etasq + eeta) + eeta * etasq);
+
+        // Secular rates from J2 and J4.
+        let cosio4 = cosio2 * cosio2;
+        let temp1 = 1.5 * J2 * pinvsq * no;
+        let temp2 = 0.5 * temp1 * J2 * pinvsq;
+        let temp3 = -0.46875 * J4 * pinvsq * pinvsq * no;
+        let mdot = no
+            + 0.5 * temp1 * rteosq * con41
+            + 0.0625 * temp2 * rteosq * (13.0 - 78.0 * cosio2 + 137.0 * cosio4);
+        let argpdot = -0.5 * temp1 * con42
+            + 0.0625 * temp2 * (7.0 - 114.0 * cosio2 + 395.0 * cosio4)
+            + temp3 * (3.0 - 36.0 * cosio2 + 49.0 * cosio4);
+        let xhdot1 = -temp1 * cosio;
+        let nodedot = xhdot1 + (0.5 * temp2 * (4.0 - 19.0 * cosio2) + 2.0 * temp3 * (3.0 - 7.0 * cosio2)) * cosio;
+
+        let omgcof = bstar * cc3 * argpo.cos();
+        let xmcof = if ecco > 1.0e-4 {
+            -2.0 / 3.0 * coef * bstar / eeta
+        } else {
+            0.0
+        };
+        let nodecf = 3.5 * omeosq * xhdot1 * cc1;
+        let t2cof = 1.5 * cc1;
+        let xlcof_denominator = if (cosio + 1.0).abs() > 1.5e-12 { 1.0 + cosio } else { 1.5e-12 };
+        let xlcof = -0.25 * J3OJ2 * sinio * (3.0 + 5.0 * cosio) / xlcof_denominator;
+        let aycof = -0.5 * J3OJ2 * sinio;
+        let delmo = (1.0 + eta * mo.cos()).powi(3);
+        let sinmao = mo.sin();
+        let x7thm1 = 7.0 * cosio2 - 1.0;
+
+        let (mut d2, mut d3, mut d4, mut t3cof, mut t4cof, mut t5cof) = (0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+        if !is_simple {
+            let cc1sq = cc1 * cc1;
+            d2 = 4.0 * ao * tsi * cc1sq;
+            let temp = d2 * tsi * cc1 / 3.0;
+            d3 = (17.0 * ao + sfour) * temp;
+            d4 = 0.5 * temp * ao * tsi * (221.0 * ao + 31.0 * sfour) * cc1;
+            t3cof = d2 + 2.0 * cc1sq;
+            t4cof = 0.25 * (3.0 * d3 + cc1 * (12.0 * d2 + 10.0 * cc1sq));
+            t5cof = 0.2 * (3.0 * d4 + 12.0 * cc1 * d3 + 6.0 * d2 * d2 + 15.0 * cc1sq * (2.0 * d2 + cc1sq));
+        }
+
+        Ok(Sgp4 {
+            tle,
+            is_simple,
+            ecco,
+            inclo,
+            nodeo,
+            argpo,
+            mo,
+            no,
+            bstar,
+            aycof,
+            xlcof,
+            con41,
+            x1mth2,
+            x7thm1,
+            cc1,
+            cc4,
+            cc5,
+            d2,
+            d3,
+            d4,
+            delmo,
+            eta,
+            argpdot,
+            mdot,
+            nodedot,
+            nodecf,
+            omgcof,
+            xmcof,
+            sinmao,
+            t2cof,
+            t3cof,
+            t4cof,
+            t5cof,
+        })
+    }
+
+    /// TEME position (km) and velocity (km/s) `minutes` after the TLE epoch.
+    pub fn propagate(&self, minutes: f64) -> Result<([f64; 3], [f64; 3]), IssError> {
+        let t = minutes;
+
+        // Secular gravity and atmospheric drag.
+        let xmdf = self.mo + self.mdot * t;
+        let argpdf = self.argpo + self.argpdot * t;
+        let nodedf = self.nodeo + self.nodedot * t;
+        let mut argpm = argpdf;
+        let mut mm = xmdf;
+        let t2 = t * t;
+        let mut nodem = nodedf + self.nodecf * t2;
+        let mut tempa = 1.0 - self.cc1 * t;
+        let mut tempe = self.bstar * self.cc4 * t;
+        let mut templ = self.t2cof * t2;
+
+        if !self.is_simple {
+            let delomg = self.omgcof * t;
+            let delm = self.xmcof * ((1.0 + self.eta * xmdf.cos()).powi(3) - self.delmo);
+            let temp = delomg + delm;
+            mm = xmdf + temp;
+            argpm = argpdf - temp;
+            let t3 = t2 * t;
+            let t4 = t3 * t;
+            tempa = tempa - self.d2 * t2 - self.d3 * t3 - self.d4 * t4;
+            tempe += self.bstar * self.cc5 * (mm.sin() - self.sinmao);
+            templ += self.t3cof * t3 + t4 * (self.t4cof + t * self.t5cof);
+        }
+
+        let am = (XKE / self.no).powf(2.0 / 3.0) * tempa * tempa;
+        let nm = XKE / am.powf(1.5);
+        let mut em = self.ecco - tempe;
+        if !(-0.001..1.0).contains(&em) {
+            return Err(IssError::Propagation(format!("eccentricity {em} out of range")));
+        }
+        em = em.max(1.0e-6);
+        mm += self.no * templ;
+        let xlm = mm + argpm + nodem;
+        nodem = nodem.rem_euclid(TAU);
+        argpm = argpm.rem_euclid(TAU);
+        let xlm = xlm.rem_euclid(TAU);
+        mm = (xlm - argpm - nodem).rem_euclid(TAU);
+
+        // Long-period periodics.
+        let sinip = self.inclo.sin();
+        let cosip = self.inclo.cos();
+        let axnl = em * argpm.cos();
+        let temp = 1.0 / (am * (1.0 - em * em));
+        let aynl = em * argpm.sin() + temp * self.aycof;
+        let xl = mm + argpm + nodem + temp * self.xlcof * axnl;
+
+        // Solve Kepler's equation.
+        let u = (xl - nodem).rem_euclid(TAU);
+        let mut eo1 = u;
+        let (mut sineo1, mut coseo1) = (0.0, 0.0);
+        for _ in 0..10 {
+            sineo1 = eo1.sin();
+            coseo1 = eo1.cos();
+            let mut step = (u - aynl * coseo1 + axnl * sineo1 - eo1) / (1.0 - coseo1 * axnl - sineo1 * aynl);
+            if step.abs() >= 0.95 {
+                step = 0.95 * step.signum();
+            }
+            eo1 += step;
+            if step.abs() < 1.0e-12 {
+                break;
+            }
+        }
+
+        // Short-period periodics.
+        let ecose = axnl * coseo1 + aynl * sineo1;
+        let esine = axnl * sineo1 - aynl * coseo1;
+        let el2 = axnl * axnl + aynl * aynl;
+        let pl = am * (1.0 - el2);
+        if pl < 0.0 {
+            return Err(IssError::Propagation(String::from("semi-latus rectum is negative")));
+        }
+        let rl = am * (1.0 - ecose);
+        let rdotl = am.sqrt() * esine / rl;
+        let rvdotl = pl.sqrt() / rl;
+        let betal = (1.0 - el2).sqrt();
+        let temp = esine / (1.0 + betal);
+        let sinu = am / rl * (sineo1 - aynl - axnl * temp);
+        let cosu = am / rl * (coseo1 - axnl + aynl * temp);
+        let su = sinu.atan2(cosu);
+        let sin2u = (cosu + cosu) * sinu;
+        let cos2u = 1.0 - 2.0 * sinu * sinu;
+        let temp = 1.0 / pl;
+        let temp1 = 0.5 * J2 * temp;
+        let temp2 = temp1 * temp;
+
+        let mrt = rl * (1.0 - 1.5 * temp2 * betal * self.con41) + 0.5 * temp1 * self.x1mth2 * cos2u;
+        if mrt < 1.0 {
+            return Err(IssError::Propagation(String::from("satellite has decayed")));
+        }
+        let su = su - 0.25 * temp2 * self.x7thm1 * sin2u;
+        let xnode = nodem + 1.5 * temp2 * cosip * sin2u;
+        let xinc = self.inclo + 1.5 * temp2 * cosip * sinip * cos2u;
+        let mvt = rdotl - nm * temp1 * self.x1mth2 * sin2u / XKE;
+        let rvdot = rvdotl + nm * temp1 * (self.x1mth2 * cos2u + 1.5 * self.con41) / XKE;
+
+        // Orientation vectors.
+        let (sinsu, cossu) = su.sin_cos();
+        let (snod, cnod) = xnode.sin_cos();
+        let (sini, cosi) = xinc.sin_cos();
+        let xmx = -snod * cosi;
+        let xmy = cnod * cosi;
+        let ux = [xmx * sinsu + cnod * cossu, xmy * sinsu + snod * cossu, sini * sinsu];
+        let vx = [xmx * cossu - cnod * sinsu, xmy * cossu - snod * sinsu, sini * cossu];
+
+        let velocity_scale = EARTH_RADIUS_KM * XKE / 60.0;
+        let mut position = [0.0; 3];
+        let mut velocity = [0.0; 3];
+        for axis in 0..3 {
+            position[axis] = mrt * ux[axis] * EARTH_RADIUS_KM;
+            velocity[axis] = (mvt * ux[axis] + rvdot * vx[axis]) * velocity_scale;
+        }
+
+        Ok((position, velocity))
+    }
+}
+
+impl Propagator for Sgp4 {
+    fn name(&self) -> &'static str {
+        "SGP4 (TLE)"
+    }
+
+    fn state_at(&self, time: DateTime<Utc>) -> Result<StateVector, IssError> {
+        let minutes = (time - self.tle.epoch).num_milliseconds() as f64 / 60_000.0;
+        let (position, velocity) = self.propagate(minutes)?;
+
+        Ok(StateVector {
+            epoch: time,
+            position,
+            velocity,
+        })
+    }
+}
@@ -0,0 +1,1438 @@
+use crate::config::{Action, Config, Keys, Screen, Theme};
+use crate::error::IssError;
+use crate::export::{ExportFormat, Tracks};
+use crate::footprint::Footprint;
+use crate::groundtrack::{predict_positions, GroundTrack};
+use crate::iss::{nearest_place, Crossing, Iss, NearestPlace};
+use crate::orbit::{geodetic_to_ecef, Geodetic, OrbitalElements, Propagator};
+use crate::passes::{compass_point, predict_passes, Observer, Pass, PASS_MIN_ELEVATION, PASS_PREDICTION_HOURS};
+use crate::provider::{Position, PositionSource, PropagatorProvider, ProviderKind};
+use crate::recorder::{Recorder, Replay, Sample};
+use crate::regions::{locate, RegionKind};
+use crate::satellite::{Satellite, ISS_NORAD_ID};
+use crate::solar::{is_sunlit, subsolar_point, terminator};
+use crate::tle::Sgp4;
+use crate::weather::{Weather, WeatherArt};
+use crate::worker::{Update, Worker};
+use chrono::prelude::*;
+use chrono::Duration;
+use crossterm::event::{self, Event, KeyCode, KeyEvent};
+use ratatui::widgets::canvas::{self, Canvas, Map, MapResolution};
+use ratatui::{prelude::*, widgets::*};
+use std::collections::VecDeque;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// A panel is marked stale once this many refreshes in a row have been missed.
+const STALE_AFTER_INTERVALS: u32 = 3;
+/// Bounds of the replay speed as changed with '<' and '>'.
+const MIN_REPLAY_SPEED: f64 = 0.125;
+const MAX_REPLAY_SPEED: f64 = 1024.0;
+/// Map colors given in turn to satellites without a `color` in the configuration.
+const SATELLITE_COLORS: [Color; 5] = [Color::Magenta, Color::LightCyan, Color::LightGreen, Color::White, Color::LightRed];
+/// Longest NORAD ID accepted on the Satellites screen.
+const MAX_NORAD_ID_DIGITS: usize = 9;
+
+/// Everything a frame shows, as plain data. [`App::view_model`] takes it from the live state;
+/// tests build one by hand and render it into a `TestBackend`.
+#[derive(Debug, Clone)]
+pub struct ViewModel {
+    pub screen: CurrentScreen,
+    /// Name of the satellite the Tracker and map follow.
+    pub name: String,
+    /// The clocks' time: now, or the recording's time during a replay.
+    pub utc: DateTime<Utc>,
+    /// `utc` in the local time zone.
+    pub local: DateTime<FixedOffset>,
+    /// How long the tracker has been running.
+    pub elapsed: Duration,
+    pub lat: f64,
+    pub lon: f64,
+    pub alt: f64,
+    /// The country, sea or ocean below.
+    pub country: String,
+    pub nearest_place: Option<NearestPlace>,
+    /// Whether the satellite is in sunlight rather than the Earth's shadow.
+    pub sunlit: bool,
+    /// Latitude and longitude where the Sun is overhead.
+    pub subsolar: (f64, f64),
+    /// The day/night boundary as (lon, lat) points.
+    pub terminator: Vec<(f64, f64)>,
+    /// Where the satellite is above the horizon.
+    pub horizon: Footprint,
+    /// Where the satellite is above the configured minimum elevation.
+    pub footprint: Footprint,
+    pub footprint_countries: Vec<String>,
+    pub alt_perigee_apogee: String,
+    pub elements: Option<OrbitalElements>,
+    pub cross_check_km: Option<f64>,
+    /// Name of the position provider.
+    pub provider: String,
+    pub source: PositionSource,
+    /// Speed of the replay, when there is one.
+    pub replay_speed: Option<f64>,
+    pub position_feed: FeedStatus,
+    pub crew_feed: FeedStatus,
+    pub weather_feed: FeedStatus,
+    pub status: Option<String>,
+    pub notice: Option<String>,
+    pub zoom: f64,
+    pub ground_track: GroundTrack,
+    pub past_track: GroundTrack,
+    pub observer: Option<Observer>,
+    pub passes: Vec<Pass>,
+    /// The ISS, then every other tracked satellite.
+    pub satellites: Vec<SatelliteView>,
+    /// Row highlighted on the Satellites screen.
+    pub selected: usize,
+    /// The NORAD ID being typed, while adding a satellite.
+    pub prompt: Option<String>,
+    /// The ISS's border crossings, oldest first.
+    pub crossings: Vec<Crossing>,
+    /// How many of the newest crossings the Events screen is scrolled past.
+    pub events_scroll: usize,
+    pub future_trajectories: String,
+    pub crew: String,
+    pub weather: Option<Weather>,
+    pub keys: Keys,
+    pub palette: Palette,
+}
+
+/// A row of the Satellites screen, and a marker on the map.
+#[derive(Debug, Clone)]
+pub struct SatelliteView {
+    pub norad_id: u32,
+    pub name: String,
+    /// None until the first position arrives.
+    pub position: Option<Position>,
+    pub country: String,
+    pub color: Color,
+    /// Whether the Tracker and map follow this satellite.
+    pub focused: bool,
+    /// The last orbit flown, drawn on the map unless the satellite is focused.
+    pub track: GroundTrack,
+}
+
+/// Tracker title badge telling whether the position came from the network or a local propagator.
+fn source_indicator(source: PositionSource) -> Span<'static> {
+    let text = format!(" {} ", source);
+    match source {
+        PositionSource::Live => text.black().on_green().bold(),
+        PositionSource::Propagated => text.black().on_yellow().bold(),
+    }
+}
+
+/// Tracker title badge shown while a recording is being replayed, with its speed.
+fn replay_indicator(speed: Option<f64>) -> Span<'static> {
+    match speed {
+        Some(speed) => format!(" REPLAY {}x ", speed).black().on_magenta().bold(),
+        None => Span::raw(""),
+    }
+}
+
+/// Next perigee/apogee countdowns from `now` and the osculating elements, for "Additional Info".
+fn orbit_summary(elements: &OrbitalElements, now: DateTime<Utc>) -> String {
+    let countdown = |time: DateTime<Utc>| {
+        let seconds = (time - now).num_seconds().max(0);
+        format!("{}m {:02}s", seconds / 60, seconds % 60)
+    };
+
+    format!(
+        "\n Perigee {:.1} km in {} \n Apogee {:.1} km in {} \n a {:.1} km  e {:.5} \n i {:.2}°  RAAN {:.2}° \n ω {:.1}°  ν {:.1}°",
+        elements.perigee_altitude(),
+        countdown(elements.next_perigee()),
+        elements.apogee_altitude(),
+        countdown(elements.next_apogee()),
+        elements.semi_major_axis,
+        elements.eccentricity,
+        elements.inclination,
+        elements.raan,
+        elements.arg_perigee,
+        elements.true_anomaly,
+    )
+}
+
+/// Weather panel text: the conditions, then their ASCII art in the colors of the weather.
+fn weather_text(weather: &Weather) -> Text<'static> {
+    let mut text = Text::from(format!(
+        "{} {}\n{}\nWind {} {} from {} \nCloud cover {}% \nPrecipitation {} {}\n",
+        weather.temperature,
+        weather.temperature_unit,
+        weather.description(),
+        weather.wind_speed,
+        weather.wind_speed_unit,
+        compass_point(weather.wind_direction),
+        weather.cloud_cover,
+        weather.precipitation,
+        weather.precipitation_unit,
+    ));
+    let art = weather.art();
+    text.extend(art.lines().iter().map(|line| Line::styled(*line, Style::default().fg(art_color(art)))));
+    text
+}
+
+fn art_color(art: WeatherArt) -> Color {
+    match art {
+        WeatherArt::Sun => Color::Yellow,
+        WeatherArt::Moon => Color::White,
+        WeatherArt::PartlyCloudyDay => Color::LightYellow,
+        WeatherArt::PartlyCloudyNight | WeatherArt::Overcast | WeatherArt::Fog => Color::Gray,
+        WeatherArt::Drizzle => Color::LightBlue,
+        WeatherArt::Rain => Color::Blue,
+        WeatherArt::FreezingRain => Color::Cyan,
+        WeatherArt::Snow => Color::White,
+        WeatherArt::Thunderstorm => Color::LightMagenta,
+        WeatherArt::OuterSpace => Color::DarkGray,
+    }
+}
+
+fn map_canvas(view: &ViewModel) -> impl Widget + 'static {
+    let (lat, lon, zoom) = (view.lat, view.lon, view.zoom);
+    let (sun_lat, sun_lon) = view.subsolar;
+    let ground_track = view.ground_track.clone();
+    let past_track = view.past_track.clone();
+    let terminator = view.terminator.clone();
+    let mut footprints = view.horizon.outline();
+    if view.footprint.min_elevation > 0.0 {
+        footprints.extend(view.footprint.outline());
+    }
+    let palette = view.palette;
+    let name = view.name.clone();
+    let color = view
+        .satellites
+        .iter()
+        .find(|satellite| satellite.focused)
+        .map_or(palette.iss, |satellite| satellite.color);
+    let others: Vec<SatelliteView> = view.satellites.iter().filter(|satellite| !satellite.focused).cloned().collect();
+    Canvas::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Current {} Position", view.name).fg(palette.title).bold()),
+        )
+        .marker(Marker::Braille)
+        .paint(move |ctx| {
+            ctx.draw(&Map {
+                color: palette.map,
+                resolution: MapResolution::High,
+            });
+            ctx.layer();
+            for pair in terminator.windows(2) {
+                ctx.draw(&canvas::Line {
+                    x1: pair[0].0,
+                    y1: pair[0].1,
+                    x2: pair[1].0,
+                    y2: pair[1].1,
+                    color: palette.terminator,
+                });
+            }
+            ctx.layer();
+            for outline in &footprints {
+                for pair in outline.windows(2) {
+                    ctx.draw(&canvas::Line {
+                        x1: pair[0].0,
+                        y1: pair[0].1,
+                        x2: pair[1].0,
+                        y2: pair[1].1,
+                        color: palette.footprint,
+                    });
+                }
+            }
+            for other in &others {
+                draw_track(ctx, &other.track, other.color);
+            }
+            draw_track(ctx, &past_track, palette.past_track);
+            draw_track(ctx, &ground_track, palette.track);
+            for (time, tick_lon, tick_lat) in &ground_track.ticks {
+                ctx.print(
+                    *tick_lon,
+                    *tick_lat,
+                    Line::from(vec![
+                        "+".fg(palette.track).bold(),
+                        time.format("%H:%M").to_string().fg(palette.past_track),
+                    ]),
+                );
+            }
+            ctx.print(sun_lon, sun_lat, "☀".fg(palette.sun).bold());
+            for other in &others {
+                if let Some(position) = other.position {
+                    ctx.print(position.lon, position.lat, other.name.clone().fg(other.color));
+                }
+            }
+            ctx.print(lon, lat, name.clone().fg(color).add_modifier(Modifier::BOLD));
+        })
+        .x_bounds([lon - zoom, lon + zoom])
+        .y_bounds([lat - zoom, lat + zoom])
+}
+fn passes_table(view: &ViewModel) -> impl Widget + '_ {
+    let title = match &view.observer {
+        Some(observer) => format!(
+            "{} passes over {} ({:.3}, {:.3})",
+            view.name, observer.name, observer.lat, observer.lon
+        ),
+        None => format!("{} passes (set an observer with --lat and --lon)", view.name),
+    };
+
+    let header = Row::new(vec!["Rise", "Az", "Culmination", "Max El", "Az", "Set", "Az", "Visible"])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+
+    let rows = view.passes.iter().map(|pass| {
+        let local = |time: DateTime<Utc>| time.with_timezone(view.local.offset()).format("%a %H:%M:%S").to_string();
+        let azimuth = |azimuth: f64| format!("{:>3.0}° {}", azimuth, compass_point(azimuth));
+        let row = Row::new(vec![
+            local(pass.rise),
+            azimuth(pass.rise_azimuth),
+            local(pass.culmination),
+            format!("{:.0}°", pass.max_elevation),
+            azimuth(pass.culmination_azimuth),
+            local(pass.set),
+            azimuth(pass.set_azimuth),
+            String::from(if pass.visible { "yes" } else { "no" }),
+        ]);
+        if pass.visible {
+            row.style(Style::default().fg(Color::Yellow))
+        } else {
+            row
+        }
+    });
+
+    let widths = [
+        Constraint::Length(12),
+        Constraint::Length(8),
+        Constraint::Length(12),
+        Constraint::Length(7),
+        Constraint::Length(8),
+        Constraint::Length(12),
+        Constraint::Length(8),
+        Constraint::Length(7),
+    ];
+
+    Table::new(rows, widths)
+        .header(header)
+        .block(Block::default().borders(Borders::ALL).title(title.fg(view.palette.title).bold()))
+}
+
+fn satellites_table(view: &ViewModel) -> impl Widget + '_ {
+    let title = format!(
+        "Satellites (Up/Down to select, Enter to follow, '{}' to add)",
+        view.keys.add_satellite
+    );
+
+    let header = Row::new(vec!["", "Name", "NORAD", "Lat", "Lon", "Alt", "Country"])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+
+    let rows = view.satellites.iter().enumerate().map(|(index, satellite)| {
+        let (lat, lon, alt) = match satellite.position {
+            Some(position) => (
+                format!("{:.3}", position.lat),
+                format!("{:.3}", position.lon),
+                format!("{:.1} km", position.alt),
+            ),
+            None => (String::from("-"), String::from("-"), String::from("-")),
+        };
+        let row = Row::new(vec![
+            Cell::from(if satellite.focused { "●" } else { "" }).style(Style::default().fg(satellite.color)),
+            Cell::from(satellite.name.clone()).style(Style::default().fg(satellite.color)),
+            Cell::from(satellite.norad_id.to_string()),
+            Cell::from(lat),
+            Cell::from(lon),
+            Cell::from(alt),
+            Cell::from(satellite.country.clone()),
+        ]);
+        if index == view.selected {
+            row.style(Style::default().add_modifier(Modifier::REVERSED))
+        } else {
+            row
+        }
+    });
+
+    let widths = [
+        Constraint::Length(1),
+        Constraint::Length(24),
+        Constraint::Length(6),
+        Constraint::Length(8),
+        Constraint::Length(8),
+        Constraint::Length(9),
+        Constraint::Length(30),
+    ];
+
+    Table::new(rows, widths)
+        .header(header)
+        .block(Block::default().borders(Borders::ALL).title(title.fg(view.palette.title).bold()))
+}
+
+/// The ISS's border crossings, newest first, scrolled down by `events_scroll`.
+fn events_log(view: &ViewModel) -> impl Widget + '_ {
+    let lines: Vec<Line> = if view.crossings.is_empty() {
+        vec![Line::from(" No border crossings yet")]
+    } else {
+        view.crossings
+            .iter()
+            .rev()
+            .map(|crossing| Line::from(format!(" {crossing}  (from {})", crossing.from)))
+            .collect()
+    };
+
+    Paragraph::new(lines).scroll((view.events_scroll as u16, 0)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("ISS Border Crossings (Up/Down to scroll)".fg(view.palette.title).bold()),
+    )
+}
+
+fn draw_track(ctx: &mut canvas::Context, track: &GroundTrack, color: Color) {
+    for segment in &track.segments {
+        for pair in segment.windows(2) {
+            ctx.draw(&canvas::Line {
+                x1: pair[0].0,
+                y1: pair[0].1,
+                x2: pair[1].0,
+                y2: pair[1].1,
+                color,
+            });
+        }
+    }
+}
+
+pub fn ui(f: &mut Frame, view: &ViewModel) {
+    // Create the layout sections.
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(1),
+            Constraint::Length(1),
+            Constraint::Length(3),
+        ])
+        .split(f.size());
+
+    let inner_layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Percentage(30), Constraint::Percentage(70)])
+        .split(chunks[1]);
+
+    let inner_layout2 = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(chunks[1]);
+
+    let footer_inner_layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[3]);
+
+    let title_block = Block::default()
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::DarkGray));
+
+    let footer_block = Block::default()
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::DarkGray));
+
+    let title_content = "rsISS";
+
+    let title = Paragraph::new(Text::styled(
+        title_content,
+        Style::default()
+            .fg(Color::White)
+            .add_modifier(Modifier::BOLD),
+    ))
+    .block(title_block);
+
+    f.render_widget(title, chunks[0]);
+
+    let footer_content = format!("CURRENT RUN TIME: {0}", view.elapsed);
+
+    let keys = &view.keys;
+    let speed = match view.replay_speed {
+        Some(_) => format!(" SPEED: '{}' '{}'", keys.slower, keys.faster),
+        None => String::new(),
+    };
+    let footer_instructions_content = format!(
+        "VIEWS: '{}' UPDATE: '{}' ZOOM: '{}' '{}' EXPORT: '{}'{}  QUIT: '{}'",
+        keys.next_screen, keys.update, keys.zoom_out, keys.zoom_in, keys.export, speed, keys.quit
+    );
+
+    let footer = Paragraph::new(Text::styled(
+        footer_content,
+        Style::default()
+            .fg(Color::White)
+            .add_modifier(Modifier::BOLD),
+    ))
+    .block(footer_block);
+
+    f.render_widget(footer, footer_inner_layout[0]);
+
+    let footer_instructions = Paragraph::new(Text::styled(
+        footer_instructions_content,
+        Style::default()
+            .fg(Color::White)
+            .add_modifier(Modifier::BOLD),
+    ))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .style(Style::default().bg(Color::DarkGray)),
+    );
+
+    f.render_widget(footer_instructions, footer_inner_layout[1]);
+
+    let status_line = match (&view.status, &view.notice) {
+        (Some(status), _) => Paragraph::new(status.as_str().red()),
+        (None, Some(notice)) => Paragraph::new(notice.as_str().green()),
+        (None, None) => Paragraph::new("OK".green()),
+    };
+
+    f.render_widget(status_line, chunks[2]);
+
+    let cross_check = match view.cross_check_km {
+        Some(km) => format!("\n OEM deviation: {:.1} km", km),
+        None => String::new(),
+    };
+
+    let orbit = view
+        .elements
+        .as_ref()
+        .map(|elements| orbit_summary(elements, view.utc))
+        .unwrap_or_default();
+
+    let sunlight = if view.sunlit { "In sunlight" } else { "In Earth's shadow" };
+    let nearest_place = match &view.nearest_place {
+        Some(place) => format!("\n Nearest place: {place}"),
+        None => String::new(),
+    };
+
+    let footprint_countries = if view.footprint_countries.is_empty() {
+        String::from("None")
+    } else {
+        view.footprint_countries.join(", ")
+    };
+
+    let tracking_widget = Paragraph::new(format!("\n Coordinates: \n LAT {0}  \n LON {1}  \n ALT {2} \n\n {14} Time: \n {3} \n Local Time: \n {4} \n\n Country: \n {5} {13} \n {10} \n\n Reachable From (above {11}°): \n {12} \n\n Additional Info: \n {6} {9} {8} \n\n Source: \n {7}", view.lat, view.lon, view.alt, view.utc, view.local, view.country, view.alt_perigee_apogee, view.provider, cross_check, orbit, sunlight, view.footprint.min_elevation, footprint_countries, nearest_place, view.name)).wrap(Wrap { trim: false }).block(Block::default().borders(Borders::ALL).title(Line::from(vec![format!("{} Tracker ", view.name).fg(view.palette.title).bold(), source_indicator(view.source), replay_indicator(view.replay_speed), view.position_feed.indicator()])));
+    let map_widget = map_canvas(view);
+    let passes_widget = passes_table(view);
+    let coordinates_widget = Paragraph::new(view.future_trajectories.as_str()).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Future Trajectories".fg(view.palette.title).bold()),
+    );
+
+    let crew_widget = Paragraph::new(view.crew.as_str()).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(Line::from(vec![format!("Current {} Crew ", view.name).fg(view.palette.title).bold(), view.crew_feed.indicator()])),
+    );
+
+    let weather_widget = Paragraph::new(view.weather.as_ref().map(weather_text).unwrap_or_default()).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(Line::from(vec![
+                "Current Weather at Coordinates ".fg(view.palette.title).bold(),
+                view.weather_feed.indicator(),
+            ])),
+    );
+
+    match view.screen {
+        CurrentScreen::Tracker => {
+            f.render_widget(tracking_widget, inner_layout[0]);
+            f.render_widget(map_widget, inner_layout[1])
+        }
+        CurrentScreen::FullMap => {
+            f.render_widget(map_widget, chunks[1]);
+        }
+
+        CurrentScreen::Passes => {
+            f.render_widget(passes_widget, inner_layout2[0]);
+            f.render_widget(coordinates_widget, inner_layout2[1])
+        }
+        CurrentScreen::Crew => {
+            f.render_widget(crew_widget, inner_layout2[0]);
+            f.render_widget(weather_widget, inner_layout2[1])
+        }
+        CurrentScreen::Satellites => f.render_widget(satellites_table(view), chunks[1]),
+        CurrentScreen::Events => f.render_widget(events_log(view), chunks[1]),
+
+        CurrentScreen::Exiting => f.render_widget(tracking_widget, chunks[1]),
+    };
+
+    if let CurrentScreen::Exiting = view.screen {
+        f.render_widget(Clear, f.size()); //this clears the entire screen and anything already drawn
+        let popup_block = Block::default()
+            .title("Y/N")
+            .borders(Borders::NONE)
+            .style(Style::default().bg(Color::DarkGray));
+
+        let exit_text = Text::styled(
+            "Would you like to quit? (y/n)",
+            Style::default().fg(Color::Red),
+        );
+        // the `trim: false` will stop the text from being cut off when over the edge of the block
+        let exit_paragraph = Paragraph::new(exit_text)
+            .block(popup_block)
+            .wrap(Wrap { trim: false });
+
+        let area = centered_rect(60, 25, f.size());
+        f.render_widget(exit_paragraph, area);
+    }
+
+    if let Some(prompt) = &view.prompt {
+        let area = centered_rect(60, 25, f.size());
+        f.render_widget(Clear, area);
+        let prompt_paragraph = Paragraph::new(format!(
+            "NORAD ID: {prompt}_\n\nEnter to track it, Esc to cancel"
+        ))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Add a satellite")
+                .style(Style::default().bg(Color::DarkGray)),
+        )
+        .wrap(Wrap { trim: false });
+        f.render_widget(prompt_paragraph, area);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurrentScreen {
+    Tracker,
+    FullMap,
+    Passes,
+    Crew,
+    Satellites,
+    Events,
+    Exiting,
+}
+
+impl CurrentScreen {
+    /// The screen the "next screen" key cycles to.
+    pub fn next(self) -> CurrentScreen {
+        match self {
+            CurrentScreen::Tracker => CurrentScreen::FullMap,
+            CurrentScreen::FullMap => CurrentScreen::Passes,
+            CurrentScreen::Passes => CurrentScreen::Crew,
+            CurrentScreen::Crew => CurrentScreen::Satellites,
+            CurrentScreen::Satellites => CurrentScreen::Events,
+            CurrentScreen::Events => CurrentScreen::Tracker,
+            CurrentScreen::Exiting => CurrentScreen::Exiting,
+        }
+    }
+}
+
+impl From<Screen> for CurrentScreen {
+    fn from(screen: Screen) -> Self {
+        match screen {
+            Screen::Tracker => CurrentScreen::Tracker,
+            Screen::Map => CurrentScreen::FullMap,
+            Screen::Passes => CurrentScreen::Passes,
+            Screen::Crew => CurrentScreen::Crew,
+            Screen::Satellites => CurrentScreen::Satellites,
+            Screen::Events => CurrentScreen::Events,
+        }
+    }
+}
+
+/// The `[theme]` colors, parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    pub title: Color,
+    pub map: Color,
+    pub track: Color,
+    pub past_track: Color,
+    pub iss: Color,
+    pub terminator: Color,
+    pub sun: Color,
+    pub footprint: Color,
+}
+
+impl Palette {
+    pub fn from_theme(theme: &Theme) -> Result<Palette, IssError> {
+        let color = |name: &str, value: &str| parse_color(&format!("theme.{name}"), value);
+
+        Ok(Palette {
+            title: color("title", &theme.title)?,
+            map: color("map", &theme.map)?,
+            track: color("track", &theme.track)?,
+            past_track: color("past_track", &theme.past_track)?,
+            iss: color("iss", &theme.iss)?,
+            terminator: color("terminator", &theme.terminator)?,
+            sun: color("sun", &theme.sun)?,
+            footprint: color("footprint", &theme.footprint)?,
+        })
+    }
+}
+
+/// A color name from the configuration, with `setting` naming where it came from in the error.
+fn parse_color(setting: &str, value: &str) -> Result<Color, IssError> {
+    value
+        .parse::<Color>()
+        .map_err(|_| IssError::InvalidConfig(format!("{setting}: unknown color '{value}'")))
+}
+
+/// A satellite tracked alongside the ISS, with the color it is drawn in.
+#[derive(Debug, Clone)]
+pub struct Tracked {
+    pub satellite: Satellite,
+    pub color: Color,
+    /// The last orbit it flew, kept from one update to the next rather than rebuilt every frame.
+    pub track: GroundTrack,
+}
+
+/// What the Tracker and map follow: the ISS, or a satellite chosen on the Satellites screen.
+struct Subject<'a> {
+    name: &'a str,
+    position: Position,
+    country: &'a str,
+    history: &'a [Position],
+    propagator: Option<&'a Arc<dyn Propagator>>,
+    elements: Option<OrbitalElements>,
+}
+
+pub struct App {
+    pub config: Config,                // settings from the configuration file and command line
+    pub palette: Palette,              // `config.theme`, parsed
+    pub current_screen: CurrentScreen, // the current screen the user is looking at, and will later determine what is rendered
+    pub status: Option<String>,        // the last fetch error, shown in the status line
+    pub notice: Option<String>,        // the last completed action, shown in the status line when there is no error
+    pub zoom: f64,                     // degrees shown either side of the ISS on the map
+    pub orbits: f64,                   // how many future orbits to predict
+    pub ground_track: GroundTrack,     // predicted track for the next `orbits` orbits
+    pub past_track: GroundTrack,       // the last orbit actually flown, from `Iss::pos_data`
+    pub observer: Option<Observer>,    // where passes are predicted for
+    pub passes: Vec<Pass>,             // upcoming passes over `observer`
+    pub footprint_countries: Vec<String>, // countries the ISS is reachable from, above `ui.footprint_min_elevation`
+    pub nearest_place: Option<NearestPlace>, // the populated place closest to the focused satellite
+    pub terminator: Vec<(f64, f64)>,   // where day meets night, as of the latest position
+    pub iss_track: GroundTrack,        // the last orbit the ISS flew, drawn while another satellite is focused
+    pub satellites: Vec<Tracked>,      // satellites tracked alongside the ISS
+    pub focus: u32,                    // NORAD ID of the satellite the Tracker and map follow
+    pub selected: usize,               // highlighted row of the Satellites screen, the ISS being row 0
+    pub prompt: Option<String>,        // the NORAD ID being typed, while adding a satellite
+    pub downloading: Vec<u32>,         // NORAD IDs whose elements have been asked for and not yet arrived
+    pub events_scroll: usize,          // how many of the newest crossings the Events screen is scrolled past
+    pub future_trajectories: String,   // the OEM's state vectors, for the Passes screen
+    pub recorder: Option<Recorder>,    // where fetched positions are logged, with `--record`
+    pub replay: Option<Replay>,        // recorded positions shown instead of the network, with `--replay`
+    pub worker: Option<Worker>,        // fetches positions, crew and weather in the background
+    pub position_feed: Feed,           // when the position last arrived, for the Tracker indicator
+    pub crew_feed: Feed,               // when the crew list last arrived
+    pub weather_feed: Feed,            // when the weather last arrived
+}
+
+/// What a panel's title badge says about its data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedStatus {
+    /// Nothing has arrived yet.
+    Loading,
+    /// Nothing has arrived and the latest fetch failed.
+    Failed,
+    /// The data is this many seconds old, or the latest fetch failed.
+    Stale(u64),
+    Fresh,
+}
+
+impl FeedStatus {
+    pub fn indicator(self) -> Span<'static> {
+        match self {
+            FeedStatus::Loading => " LOADING ".black().on_yellow().bold(),
+            FeedStatus::Failed => " FAILED ".black().on_red().bold(),
+            FeedStatus::Stale(secs) => format!(" STALE {}s ", secs).black().on_red().bold(),
+            FeedStatus::Fresh => Span::raw(""),
+        }
+    }
+}
+
+/// When a panel's data last arrived, so the panel can say it is loading or stale.
+#[derive(Debug, Clone, Copy)]
+pub struct Feed {
+    updated: Option<Instant>,
+    failed: bool,
+    stale_after: std::time::Duration,
+}
+
+impl Feed {
+    pub fn new(stale_after: std::time::Duration) -> Feed {
+        Feed {
+            updated: None,
+            failed: false,
+            stale_after,
+        }
+    }
+
+    pub fn record(&mut self, ok: bool) {
+        if ok {
+            self.updated = Some(Instant::now());
+        }
+        self.failed = !ok;
+    }
+
+    /// Loading until the first data arrives, stale once it is old or the latest fetch failed,
+    /// and fresh otherwise.
+    pub fn status(&self) -> FeedStatus {
+        match self.updated {
+            None if self.failed => FeedStatus::Failed,
+            None => FeedStatus::Loading,
+            Some(updated) if self.failed || updated.elapsed() > self.stale_after => {
+                FeedStatus::Stale(updated.elapsed().as_secs())
+            }
+            Some(_) => FeedStatus::Fresh,
+        }
+    }
+}
+
+impl App {
+    pub fn new(config: Config) -> Result<App, IssError> {
+        let schedule = config.intervals.schedule();
+        for satellite in &config.satellites {
+            if let Some(color) = &satellite.color {
+                parse_color(&format!("satellites.{}.color", satellite.norad_id), color)?;
+            }
+        }
+
+        Ok(App {
+            palette: Palette::from_theme(&config.theme)?,
+            current_screen: config.ui.screen.into(),
+            status: None,
+            notice: None,
+            zoom: config.ui.zoom,
+            orbits: config.ui.orbits,
+            ground_track: GroundTrack::default(),
+            past_track: GroundTrack::default(),
+            observer: config.observer.observer(),
+            passes: Vec::new(),
+            footprint_countries: Vec::new(),
+            nearest_place: None,
+            terminator: terminator(Utc::now()),
+            iss_track: GroundTrack::default(),
+            satellites: Vec::new(),
+            focus: ISS_NORAD_ID,
+            selected: 0,
+            prompt: None,
+            downloading: Vec::new(),
+            events_scroll: 0,
+            future_trajectories: String::new(),
+            recorder: None,
+            replay: None,
+            worker: None,
+            position_feed: Feed::new(STALE_AFTER_INTERVALS * schedule.position),
+            crew_feed: Feed::new(STALE_AFTER_INTERVALS * schedule.crew),
+            weather_feed: Feed::new(STALE_AFTER_INTERVALS * schedule.weather),
+            config,
+        })
+    }
+
+    /// What the next frame shows, `elapsed` after the tracker started.
+    pub fn view_model(&self, iss: &Iss, elapsed: Duration) -> ViewModel {
+        let utc = self.clock();
+        let subject = self.subject(iss);
+        let Position { lat, lon, alt, .. } = subject.position;
+
+        // Other satellites are propagated locally, so only the ISS has a provider and cross-check.
+        let (alt_perigee_apogee, cross_check_km, provider, source) = match self.focused() {
+            None => (iss.alt_perigee_apogee.clone(), iss.cross_check_km, iss.provider.name(), iss.source),
+            Some(satellite) => (
+                match &satellite.elements {
+                    Some(elements) if elements.is_ascending() => String::from("Approaching Apogee"),
+                    Some(_) => String::from("Approaching Perigee"),
+                    None => String::new(),
+                },
+                None,
+                satellite.propagator.name(),
+                PositionSource::Propagated,
+            ),
+        };
+
+        // The crew list and the position feed are the ISS's; other satellites are never stale.
+        let (crew, position_feed, crew_feed) = match self.focused() {
+            None => (iss.crew.clone(), self.position_feed.status(), self.crew_feed.status()),
+            Some(satellite) => (
+                format!("No crew list is available for {}", satellite.name),
+                FeedStatus::Fresh,
+                FeedStatus::Fresh,
+            ),
+        };
+
+        let mut satellites = vec![SatelliteView {
+            norad_id: ISS_NORAD_ID,
+            name: String::from("ISS"),
+            position: iss.pos_data.last().copied(),
+            country: iss.country.clone(),
+            color: self.palette.iss,
+            focused: self.focus == ISS_NORAD_ID,
+            track: self.iss_track.clone(),
+        }];
+        satellites.extend(self.satellites.iter().map(|tracked| SatelliteView {
+            norad_id: tracked.satellite.norad_id,
+            name: tracked.satellite.name.clone(),
+            position: tracked.satellite.position,
+            country: tracked.satellite.country.clone(),
+            color: tracked.color,
+            focused: self.focus == tracked.satellite.norad_id,
+            track: tracked.track.clone(),
+        }));
+
+        ViewModel {
+            screen: self.current_screen,
+            name: subject.name.to_string(),
+            utc,
+            local: utc.with_timezone(&Local).fixed_offset(),
+            elapsed,
+            lat,
+            lon,
+            alt,
+            country: subject.country.to_string(),
+            nearest_place: self.nearest_place.clone(),
+            sunlit: is_sunlit(&geodetic_to_ecef(&Geodetic { lat, lon, alt }), utc),
+            subsolar: subsolar_point(utc),
+            terminator: self.terminator.clone(),
+            horizon: Footprint::new(lat, lon, alt, 0.0),
+            footprint: Footprint::new(lat, lon, alt, self.config.ui.footprint_min_elevation),
+            footprint_countries: self.footprint_countries.clone(),
+            alt_perigee_apogee,
+            elements: subject.elements,
+            cross_check_km,
+            provider: provider.to_string(),
+            source,
+            replay_speed: self.replay.as_ref().map(|replay| replay.speed),
+            position_feed,
+            crew_feed,
+            weather_feed: self.weather_feed.status(),
+            status: self.status.clone(),
+            notice: self.notice.clone(),
+            zoom: self.zoom,
+            ground_track: self.ground_track.clone(),
+            past_track: self.past_track.clone(),
+            observer: self.observer.clone(),
+            passes: self.passes.clone(),
+            satellites,
+            selected: self.selected,
+            prompt: self.prompt.clone(),
+            crossings: iss.crossings.clone(),
+            events_scroll: self.events_scroll,
+            future_trajectories: self.future_trajectories.clone(),
+            crew,
+            weather: iss.weather.clone(),
+            keys: self.config.keys,
+            palette: self.palette,
+        }
+    }
+
+    /// Moves the ISS on from the replay when there is one, and otherwise asks the worker
+    /// for a fresh position, which arrives later through [`App::poll_worker`].
+    pub fn update_position(&mut self, iss: &mut Iss) {
+        let Some(replay) = &mut self.replay else {
+            if let Some(worker) = &self.worker {
+                worker.refresh_position();
+            }
+            return;
+        };
+
+        let samples = replay.advance().to_vec();
+        let finished = replay.is_finished();
+        for sample in samples {
+            // The region was looked up when the sample was recorded.
+            let country = (sample.country != "Unknown Country").then_some(sample.country.clone());
+            iss.set_position_over(sample.position(), sample.source, country);
+            self.position_moved(iss, Ok(()));
+        }
+        if finished {
+            self.status = Some(String::from("End of replay"));
+        }
+    }
+
+    /// Applies everything the worker has fetched since the last call.
+    pub fn poll_worker(&mut self, iss: &mut Iss) {
+        let updates: Vec<Update> = match &mut self.worker {
+            Some(worker) => std::iter::from_fn(|| worker.try_recv()).collect(),
+            None => Vec::new(),
+        };
+
+        for update in updates {
+            match update {
+                Update::Position(result) => self.apply_position(iss, result),
+                Update::Crew(result) => {
+                    self.crew_feed.record(result.is_ok());
+                    match result {
+                        Ok(crew) => iss.crew = crew.join("\n"),
+                        Err(err) => self.set_status(Err(err)),
+                    }
+                }
+                Update::Weather(result) => {
+                    self.weather_feed.record(result.is_ok());
+                    match result {
+                        Ok(weather) => iss.weather = Some(weather),
+                        Err(err) => self.set_status(Err(err)),
+                    }
+                }
+                Update::Tle(norad_id, result) => {
+                    self.downloading.retain(|&downloading| downloading != norad_id);
+                    let configured = self.config.satellites.iter().find(|satellite| satellite.norad_id == norad_id);
+                    let name = configured.and_then(|satellite| satellite.name.clone());
+                    // Configured colors were checked in `App::new`.
+                    let color = configured
+                        .and_then(|satellite| satellite.color.as_deref())
+                        .and_then(|color| color.parse::<Color>().ok());
+                    match result.and_then(|tle| Satellite::from_tle(tle, name)) {
+                        Ok(satellite) => self.add_satellite(satellite, color),
+                        Err(err) => self.set_status(Err(err)),
+                    }
+                }
+                Update::IssTle(result) => match result.and_then(Sgp4::new) {
+                    Ok(sgp4) => {
+                        let sgp4: Arc<dyn Propagator> = Arc::new(sgp4);
+                        iss.propagator = Some(sgp4.clone());
+                        self.install_propagator(iss, sgp4, ProviderKind::Tle);
+                    }
+                    Err(err) => self.set_status(Err(err)),
+                },
+                Update::Oem(result) => match result {
+                    Ok((ephemeris, coordinates)) => {
+                        self.future_trajectories = coordinates;
+                        let oem: Arc<dyn Propagator> = Arc::new(ephemeris);
+                        iss.cross_check = Some(oem.clone());
+                        // SGP4 from the TLE is preferred for the tracks when it is there too.
+                        iss.propagator.get_or_insert_with(|| oem.clone());
+                        self.install_propagator(iss, oem, ProviderKind::Oem);
+                    }
+                    Err(err) => self.set_status(Err(err)),
+                },
+            }
+        }
+    }
+
+    /// Reads positions from a newly downloaded ephemeris when `kind` is the configured provider,
+    /// and lets the worker fall back to the ISS's propagator.
+    fn install_propagator(&mut self, iss: &mut Iss, propagator: Arc<dyn Propagator>, kind: ProviderKind) {
+        if self.config.providers.default == kind {
+            iss.provider = Box::new(PropagatorProvider::new(propagator));
+        }
+        if let Some(worker) = &self.worker {
+            worker.set_provider(iss.provider.box_clone(), iss.propagator.clone());
+        }
+        self.refresh_tracks(iss);
+    }
+
+    /// Moves the ISS to a fetched position, records it and refreshes the tracks.
+    fn apply_position(&mut self, iss: &mut Iss, result: Result<(Position, PositionSource), IssError>) {
+        let result = result.map(|(position, source)| iss.set_position(position, source));
+        self.position_moved(iss, result);
+    }
+
+    /// Records the position the ISS has just moved to, or the failure to move it, and
+    /// refreshes the tracks.
+    fn position_moved(&mut self, iss: &mut Iss, result: Result<(), IssError>) {
+        let result = result.and_then(|()| match &mut self.recorder {
+            Some(recorder) => recorder.record(&Sample::from_iss(iss)),
+            None => Ok(()),
+        });
+
+        self.position_feed.record(result.is_ok());
+        self.set_status(result);
+        if let Some(worker) = &self.worker {
+            worker.set_location(iss.lat, iss.lon);
+        }
+        self.refresh_tracks(iss);
+    }
+
+    /// The clocks' time: now, or the recording's time during a replay.
+    fn clock(&self) -> DateTime<Utc> {
+        match &self.replay {
+            Some(replay) => replay.clock(),
+            None => Utc::now(), // e.g. `2014-11-28T12:45:59.324310806Z`
+        }
+    }
+
+    /// The focused satellite, unless it is the ISS.
+    fn focused(&self) -> Option<&Satellite> {
+        self.satellites
+            .iter()
+            .map(|tracked| &tracked.satellite)
+            .find(|satellite| satellite.norad_id == self.focus)
+    }
+
+    fn subject<'a>(&'a self, iss: &'a Iss) -> Subject<'a> {
+        match self.focused() {
+            Some(satellite) => Subject {
+                name: &satellite.name,
+                position: satellite.position.unwrap_or(Position {
+                    lat: 0.0,
+                    lon: 0.0,
+                    alt: 0.0,
+                    timestamp: 0.0,
+                }),
+                country: &satellite.country,
+                history: &satellite.history,
+                propagator: Some(&satellite.propagator),
+                elements: satellite.elements,
+            },
+            None => Subject {
+                name: "ISS",
+                position: Position {
+                    lat: iss.lat,
+                    lon: iss.lon,
+                    alt: iss.alt,
+                    timestamp: iss.time,
+                },
+                country: &iss.country,
+                history: &iss.pos_data,
+                propagator: iss.propagator.as_ref(),
+                elements: iss.elements,
+            },
+        }
+    }
+
+    /// The last orbit in `history` before the clocks' time, drawn as a track.
+    fn flown_track(&self, history: &[Position]) -> GroundTrack {
+        GroundTrack::from_history(last_orbit(history, self.clock()))
+    }
+
+    /// Recomputes the predicted and past ground tracks around the focused satellite's position,
+    /// and everything else shown about where it is, so frames in between only redraw them.
+    pub fn refresh_tracks(&mut self, iss: &Iss) {
+        let subject = self.subject(iss);
+        let Position { lat, lon, alt, timestamp } = subject.position;
+
+        // Predicted from the displayed position's time, which lags the clock during a replay.
+        let start = DateTime::from_timestamp(timestamp as i64, 0).unwrap_or_else(Utc::now);
+        let ground_track = match subject.propagator {
+            Some(propagator) => GroundTrack::predict(propagator.as_ref(), start, self.orbits),
+            None => GroundTrack::default(),
+        };
+        let past_track = self.flown_track(subject.history);
+
+        let footprint = Footprint::new(lat, lon, alt, self.config.ui.footprint_min_elevation);
+        let footprint_countries = footprint.countries(|lat, lon| {
+            locate(lat, lon)
+                .filter(|region| region.kind == RegionKind::Country)
+                .map(|region| region.name.clone())
+        });
+
+        // Passes only change once one is over, so they are not recomputed every refresh.
+        let now = self.clock();
+        let passes = match self.passes.first() {
+            Some(pass) if pass.set >= now => None,
+            _ => Some(match (subject.propagator, &self.observer) {
+                (Some(propagator), Some(observer)) => predict_passes(
+                    propagator.as_ref(),
+                    observer,
+                    now,
+                    PASS_PREDICTION_HOURS,
+                    PASS_MIN_ELEVATION,
+                ),
+                _ => Vec::new(),
+            }),
+        };
+
+        self.nearest_place = nearest_place(lat, lon);
+        self.terminator = terminator(self.clock());
+        self.iss_track = self.flown_track(&iss.pos_data);
+        self.ground_track = ground_track;
+        self.past_track = past_track;
+        self.footprint_countries = footprint_countries;
+        if let Some(passes) = passes {
+            self.passes = passes;
+        }
+    }
+
+    /// Downloads the elements of `norad_id` so it is tracked once they arrive.
+    pub fn track(&mut self, norad_id: u32) {
+        if norad_id == ISS_NORAD_ID || self.satellites.iter().any(|tracked| tracked.satellite.norad_id == norad_id) {
+            self.notice = Some(format!("Already tracking NORAD {norad_id}"));
+            return;
+        }
+        if self.downloading.contains(&norad_id) {
+            self.notice = Some(format!("Already downloading the elements of NORAD {norad_id}"));
+            return;
+        }
+        if let Some(worker) = &self.worker {
+            worker.fetch_tle(norad_id, self.config.providers.tle_url_for(norad_id));
+            self.downloading.push(norad_id);
+            self.notice = Some(format!("Downloading the elements of NORAD {norad_id}"));
+        }
+    }
+
+    /// Tracks `satellite` from now on, drawn in `color` or else the next unused default.
+    pub fn add_satellite(&mut self, mut satellite: Satellite, color: Option<Color>) {
+        let color = color.unwrap_or(SATELLITE_COLORS[self.satellites.len() % SATELLITE_COLORS.len()]);
+        let result = satellite.update(self.clock());
+        self.notice = Some(format!("Tracking {} (NORAD {})", satellite.name, satellite.norad_id));
+        let track = self.flown_track(&satellite.history);
+        self.satellites.push(Tracked { satellite, color, track });
+        self.set_status(result);
+    }
+
+    /// Moves every other satellite to the clocks' time, and the tracks with the focused one.
+    pub fn update_satellites(&mut self, iss: &Iss) {
+        let time = self.clock();
+        let mut result = Ok(());
+        for tracked in &mut self.satellites {
+            result = result.and(tracked.satellite.update(time));
+            tracked.track = GroundTrack::from_history(last_orbit(&tracked.satellite.history, time));
+        }
+        if result.is_err() {
+            self.set_status(result);
+        }
+        if self.focus != ISS_NORAD_ID {
+            self.refresh_tracks(iss);
+        }
+    }
+
+    /// Makes the Tracker and map follow the satellite on row `index` of the Satellites screen.
+    pub fn focus_on(&mut self, index: usize, iss: &Iss) {
+        let norad_id = match index {
+            0 => ISS_NORAD_ID,
+            _ => match self.satellites.get(index - 1) {
+                Some(tracked) => tracked.satellite.norad_id,
+                None => return,
+            },
+        };
+        if norad_id != self.focus {
+            self.focus = norad_id;
+            // The passes were over the previous satellite.
+            self.passes.clear();
+            self.refresh_tracks(iss);
+        }
+    }
+
+    /// The ISS's flown track and the next `orbits` orbits predicted from its position.
+    pub fn tracks(&self, iss: &Iss) -> Tracks {
+        let start = DateTime::from_timestamp(iss.time as i64, 0).unwrap_or_else(Utc::now);
+        // The OEM is NASA's own prediction, so it is preferred over the TLE when loaded.
+        Tracks {
+            past: iss.pos_data.clone(),
+            predicted: match iss.cross_check.as_ref().or(iss.propagator.as_ref()) {
+                Some(propagator) => predict_positions(propagator.as_ref(), start, self.orbits),
+                None => Vec::new(),
+            },
+        }
+    }
+
+    /// Writes the flown and predicted tracks to GeoJSON, KML and GPX files in the working directory.
+    pub fn export_tracks(&mut self, iss: &Iss) {
+        let tracks = self.tracks(iss);
+        let stem = format!("iss-track-{}", Local::now().format("%Y%m%d-%H%M%S"));
+        for format in ExportFormat::ALL {
+            let path = format!("{stem}.{}", format.extension());
+            if let Err(err) = tracks.write(Path::new(&path), format) {
+                return self.set_status(Err(err));
+            }
+        }
+        self.notice = Some(format!("Exported {stem}.geojson, .kml and .gpx"));
+    }
+
+    /// Records the outcome of a fetch: errors are shown until the next successful update.
+    pub fn set_status(&mut self, result: Result<(), IssError>) {
+        match result {
+            Ok(()) => self.status = None,
+            Err(err) => {
+                self.status = Some(format!("{} (at {})", err, Local::now().format("%H:%M:%S")))
+            }
+        }
+    }
+}
+
+/// Where [`run_app`] reads key presses from.
+pub trait EventSource {
+    /// The next event, or `None` once `timeout` has passed without one.
+    fn next(&mut self, timeout: std::time::Duration) -> io::Result<Option<Event>>;
+}
+
+/// The terminal's events.
+#[derive(Debug, Default)]
+pub struct CrosstermEvents;
+
+impl EventSource for CrosstermEvents {
+    fn next(&mut self, timeout: std::time::Duration) -> io::Result<Option<Event>> {
+        if event::poll(timeout)? {
+            Ok(Some(event::read()?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// A fixed script of events, for driving [`run_app`] without a terminal. `None` stands for a
+/// poll that timed out. Reading past the end fails, which ends a script that never quits.
+#[derive(Debug, Default)]
+pub struct ScriptedEvents {
+    events: VecDeque<Option<Event>>,
+}
+
+impl ScriptedEvents {
+    pub fn new(events: impl IntoIterator<Item = Option<Event>>) -> Self {
+        ScriptedEvents {
+            events: events.into_iter().collect(),
+        }
+    }
+
+    /// One key press per character of `keys`.
+    pub fn keys(keys: &str) -> Self {
+        ScriptedEvents::new(keys.chars().map(|key| Some(Event::Key(KeyEvent::from(KeyCode::Char(key))))))
+    }
+}
+
+impl EventSource for ScriptedEvents {
+    fn next(&mut self, _timeout: std::time::Duration) -> io::Result<Option<Event>> {
+        self.events
+            .pop_front()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "end of scripted events"))
+    }
+}
+
+/// Draws the tracker and handles key presses until the user confirms quitting.
+pub fn run_app<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    iss: &mut Iss,
+    events: &mut dyn EventSource,
+    start_time: DateTime<Local>,
+) -> io::Result<bool> {
+    let mut duration = 0;
+    loop {
+        app.poll_worker(iss);
+        let elapsed_time: Duration = Local::now() - start_time;
+        let view = app.view_model(iss, elapsed_time);
+
+        terminal.draw(|f| ui(f, &view))?;
+
+        match events.next(app.config.intervals.poll())? {
+            Some(Event::Key(key)) => {
+                if key.kind == event::KeyEventKind::Release {
+                    // Skip events that are not KeyEventKind::Press
+                    continue;
+                }
+                // While a NORAD ID is being typed, every key goes to the prompt.
+                if let Some(prompt) = &mut app.prompt {
+                    match key.code {
+                        KeyCode::Char(digit) if digit.is_ascii_digit() && prompt.len() < MAX_NORAD_ID_DIGITS => {
+                            prompt.push(digit)
+                        }
+                        KeyCode::Backspace => {
+                            prompt.pop();
+                        }
+                        KeyCode::Enter => {
+                            if let Ok(norad_id) = prompt.parse() {
+                                app.track(norad_id);
+                            }
+                            app.prompt = None;
+                        }
+                        KeyCode::Esc => app.prompt = None,
+                        _ => {}
+                    }
+                    continue;
+                }
+                let action = match key.code {
+                    KeyCode::Char(key) => app.config.keys.action(key),
+                    _ => None,
+                };
+                match (app.current_screen, action) {
+                    (CurrentScreen::Exiting, _) => match key.code {
+                        KeyCode::Char('y') => {
+                            return Ok(true);
+                        }
+                        KeyCode::Char('n') => {
+                            app.current_screen = CurrentScreen::Tracker; // reset if decide not to quit
+                        }
+                        _ if action == Some(Action::Quit) => {
+                            app.current_screen = CurrentScreen::Tracker;
+                        }
+                        _ => {}
+                    },
+                    (screen, Some(Action::NextScreen)) => {
+                        app.current_screen = screen.next();
+                    }
+                    (_, Some(Action::Quit)) => {
+                        app.current_screen = CurrentScreen::Exiting;
+                    }
+                    (_, Some(Action::Update)) => {
+                        app.update_position(iss);
+                        app.update_satellites(iss);
+                    }
+                    (CurrentScreen::Satellites, Some(Action::AddSatellite)) => {
+                        app.prompt = Some(String::new());
+                    }
+                    (CurrentScreen::Events, None) => match key.code {
+                        KeyCode::Up => app.events_scroll = app.events_scroll.saturating_sub(1),
+                        KeyCode::Down => {
+                            app.events_scroll = (app.events_scroll + 1).min(iss.crossings.len().saturating_sub(1))
+                        }
+                        _ => {}
+                    },
+                    (CurrentScreen::Satellites, None) => match key.code {
+                        KeyCode::Up => app.selected = app.selected.saturating_sub(1),
+                        KeyCode::Down => app.selected = (app.selected + 1).min(app.satellites.len()),
+                        KeyCode::Enter => app.focus_on(app.selected, iss),
+                        _ => {}
+                    },
+                    // Zooming and exporting only make sense where the map is shown.
+                    (CurrentScreen::Tracker | CurrentScreen::FullMap, Some(Action::ZoomIn)) => {
+                        app.zoom -= 10.0;
+                    }
+                    (CurrentScreen::Tracker | CurrentScreen::FullMap, Some(Action::ZoomOut)) => {
+                        app.zoom += 10.0;
+                    }
+                    (CurrentScreen::Tracker | CurrentScreen::FullMap, Some(Action::Export)) => {
+                        app.export_tracks(iss);
+                    }
+                    (_, Some(Action::Faster)) => {
+                        if let Some(replay) = &mut app.replay {
+                            replay.speed = (replay.speed * 2.0).min(MAX_REPLAY_SPEED);
+                        }
+                    }
+                    (_, Some(Action::Slower)) => {
+                        if let Some(replay) = &mut app.replay {
+                            replay.speed = (replay.speed / 2.0).max(MIN_REPLAY_SPEED);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Some(_) => {}
+            None => {
+                duration += app.config.intervals.poll_ms;
+
+                // Live positions arrive from the worker on its own schedule; only a replay needs driving.
+                // The other satellites are propagated locally, so they move on every refresh.
+                if duration >= app.config.intervals.refresh_ms {
+                    if app.replay.is_some() {
+                        app.update_position(iss);
+                    }
+                    app.update_satellites(iss);
+                    duration = 0;
+                }
+            }
+        }
+    }
+}
+
+/// The end of `history` flown in roughly one orbit (~93 minutes) up to `clock`, however often
+/// it was sampled.
+fn last_orbit(history: &[Position], clock: DateTime<Utc>) -> &[Position] {
+    let since = (clock - Duration::minutes(93)).timestamp() as f64;
+    &history[history.partition_point(|position| position.timestamp < since)..]
+}
+
+/// helper function to create a centered rect using up certain percentage of the available rect `r`
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    // Cut the given rectangle into three vertical pieces
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    // Then cut the middle vertical piece into three width-wise pieces
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1] // Return the middle chunk
+}
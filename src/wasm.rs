@@ -1,19 +1,67 @@
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::future_to_promise;
 use serde::Serialize;
-use crate::iss::Iss;
+use std::cell::RefCell;
+use std::rc::Rc;
+use crate::iss::{get_crew_async, get_position_async, get_weather_async, Iss, OPEN_NOTIFY_CREW_URL};
+use crate::position::Position;
+use crate::weather::{Weather, WeatherUnits, OPEN_METEO_URL};
 
 #[derive(Serialize)]
 pub struct IssPosition {
     lat: f64,
     lon: f64,
     alt: f64,
+    country: String,
     timestamp: String,
 }
 
+impl IssPosition {
+    fn of(iss: &Iss) -> IssPosition {
+        let time = chrono::DateTime::from_timestamp_millis((iss.time * 1000.0) as i64).unwrap_or_else(chrono::Utc::now);
+        IssPosition {
+            lat: iss.lat,
+            lon: iss.lon,
+            alt: iss.alt,
+            country: iss.country.clone(),
+            timestamp: time.to_rfc3339(),
+        }
+    }
+}
+
+/// The weather below the ISS, with its description.
+#[derive(Serialize)]
+struct IssWeather<'a> {
+    description: String,
+    #[serde(flatten)]
+    weather: &'a Weather,
+}
+
+fn to_js(value: &impl Serialize) -> Result<JsValue, JsValue> {
+    Ok(serde_wasm_bindgen::to_value(value)?)
+}
+
+fn weather_to_js(weather: Option<&Weather>) -> Result<JsValue, JsValue> {
+    match weather {
+        Some(weather) => to_js(&IssWeather {
+            description: weather.description(),
+            weather,
+        }),
+        None => Ok(JsValue::NULL),
+    }
+}
+
+fn crew_to_js(crew: &str) -> Result<JsValue, JsValue> {
+    to_js(&crew.lines().collect::<Vec<_>>())
+}
+
+/// The ISS as seen from JavaScript. The state is shared with the futures behind the returned
+/// promises, so an update lands in the tracker itself rather than in a copy.
 #[wasm_bindgen]
 pub struct IssTracker {
-    iss: Iss,
+    iss: Rc<RefCell<Iss>>,
+    /// Called with the new `IssPosition` after every update.
+    listeners: Rc<RefCell<Vec<js_sys::Function>>>,
 }
 
 #[wasm_bindgen]
@@ -21,49 +69,146 @@ impl IssTracker {
     #[wasm_bindgen(constructor)]
     pub fn new() -> IssTracker {
         console_error_panic_hook::set_once();
-        
+
         let mut iss = Iss::new();
         iss.alt = 417.5;
-        
-        IssTracker { iss }
+
+        IssTracker {
+            iss: Rc::new(RefCell::new(iss)),
+            listeners: Rc::new(RefCell::new(Vec::new())),
+        }
     }
 
     #[wasm_bindgen]
-    pub fn get_position(&self) -> JsValue {
-        let position = IssPosition {
-            lat: self.iss.lat,
-            lon: self.iss.lon,
-            alt: self.iss.alt,
-            timestamp: chrono::Utc::now().to_rfc3339(),
-        };
-        
-        serde_wasm_bindgen::to_value(&position).unwrap()
+    pub fn get_position(&self) -> Result<JsValue, JsValue> {
+        to_js(&IssPosition::of(&self.iss.borrow()))
     }
 
     #[wasm_bindgen]
     pub fn get_coordinates_string(&self) -> String {
-        format!("LAT: {:.4}, LON: {:.4}, ALT: {:.1} km", 
-                self.iss.lat, self.iss.lon, self.iss.alt)
+        let iss = self.iss.borrow();
+        format!("LAT: {:.4}, LON: {:.4}, ALT: {:.1} km",
+                iss.lat, iss.lon, iss.alt)
+    }
+
+    #[wasm_bindgen]
+    pub fn get_country(&self) -> String {
+        self.iss.borrow().country.clone()
+    }
+
+    /// Every position since the page loaded, oldest first.
+    #[wasm_bindgen]
+    pub fn get_history(&self) -> Result<JsValue, JsValue> {
+        to_js(&self.iss.borrow().pos_data)
+    }
+
+    /// The names of the people in space, empty until `update_crew_async` has finished.
+    #[wasm_bindgen]
+    pub fn get_crew(&self) -> Result<JsValue, JsValue> {
+        crew_to_js(&self.iss.borrow().crew)
+    }
+
+    /// The weather below the ISS, or null until `update_weather_async` has finished.
+    #[wasm_bindgen]
+    pub fn get_weather(&self) -> Result<JsValue, JsValue> {
+        weather_to_js(self.iss.borrow().weather.as_ref())
+    }
+
+    /// Calls `callback` with the new `IssPosition` after every update, however it was made.
+    #[wasm_bindgen]
+    pub fn on_update(&self, callback: js_sys::Function) {
+        self.listeners.borrow_mut().push(callback);
     }
 
-    // Async version that fetches real data
+    /// Fetches the position from wheretheiss.at and moves the tracker there. The promise
+    /// resolves to the new `IssPosition`, once the `on_update` callbacks have seen it.
     #[wasm_bindgen]
-    pub fn update_position_async(&mut self) -> js_sys::Promise {
-        let mut iss_clone = self.iss.clone();
-        
+    pub fn update_position_async(&self) -> js_sys::Promise {
+        let iss = Rc::clone(&self.iss);
+        let listeners = Rc::clone(&self.listeners);
+
         future_to_promise(async move {
-            match iss_clone.update_position_async().await {
-                Ok(_) => Ok(JsValue::from("Success")),
-                Err(e) => Err(JsValue::from_str(&format!("Error: {}", e)))
-            }
+            // The state is only borrowed once the fetch is over, so updates may overlap.
+            let position = get_position_async()
+                .await
+                .map_err(|e| JsValue::from_str(&format!("Error: {}", e)))?;
+            set_position(&iss, &listeners, position)
         })
     }
 
-    // Method to update the internal state after async operation
+    /// Fetches the people in space from open-notify. The promise resolves to their names.
     #[wasm_bindgen]
-    pub fn set_position(&mut self, lat: f64, lon: f64, alt: f64) {
-        self.iss.lat = lat;
-        self.iss.lon = lon;
-        self.iss.alt = alt;
+    pub fn update_crew_async(&self) -> js_sys::Promise {
+        let iss = Rc::clone(&self.iss);
+
+        future_to_promise(async move {
+            let crew = get_crew_async(OPEN_NOTIFY_CREW_URL)
+                .await
+                .map_err(|e| JsValue::from_str(&format!("Error: {}", e)))?;
+            let crew = crew.join("\n");
+            let value = crew_to_js(&crew)?;
+            iss.borrow_mut().crew = crew;
+            Ok(value)
+        })
     }
-}
\ No newline at end of file
+
+    /// Fetches the weather below the ISS's current position from Open-Meteo. The promise
+    /// resolves to the report, with its description.
+    #[wasm_bindgen]
+    pub fn update_weather_async(&self) -> js_sys::Promise {
+        let iss = Rc::clone(&self.iss);
+        let (lat, lon) = {
+            let iss = self.iss.borrow();
+            (iss.lat, iss.lon)
+        };
+
+        future_to_promise(async move {
+            let weather = get_weather_async(OPEN_METEO_URL, lat, lon, &WeatherUnits::default())
+                .await
+                .map_err(|e| JsValue::from_str(&format!("Error: {}", e)))?;
+            let value = weather_to_js(Some(&weather))?;
+            iss.borrow_mut().weather = Some(weather);
+            Ok(value)
+        })
+    }
+
+    /// Moves the tracker to a position fetched elsewhere.
+    #[wasm_bindgen]
+    pub fn set_position(&self, lat: f64, lon: f64, alt: f64) -> Result<JsValue, JsValue> {
+        let position = Position {
+            lat,
+            lon,
+            alt,
+            timestamp: chrono::Utc::now().timestamp_millis() as f64 / 1000.0,
+        };
+        set_position(&self.iss, &self.listeners, position)
+    }
+}
+
+impl Default for IssTracker {
+    fn default() -> Self {
+        IssTracker::new()
+    }
+}
+
+/// Moves `iss` and tells the `listeners`, returning the new `IssPosition`.
+fn set_position(
+    iss: &RefCell<Iss>,
+    listeners: &RefCell<Vec<js_sys::Function>>,
+    new_position: Position,
+) -> Result<JsValue, JsValue> {
+    let position = {
+        let mut iss = iss.borrow_mut();
+        iss.set_position(new_position);
+        to_js(&IssPosition::of(&iss))?
+    };
+
+    // A copy, so a callback may register another without a double borrow.
+    let listeners = listeners.borrow().clone();
+    for listener in &listeners {
+        if let Err(err) = listener.call1(&JsValue::NULL, &position) {
+            web_sys::console::error_1(&err);
+        }
+    }
+    Ok(position)
+}
@@ -0,0 +1,469 @@
+use serde::{Deserialize, Serialize};
+
+/// Open-Meteo forecast endpoint.
+pub const OPEN_METEO_URL: &str = "https://api.open-meteo.com/v1/forecast";
+
+/// Current conditions requested from Open-Meteo, in the order of the [`Current`] fields.
+const CURRENT_VARIABLES: &str =
+    "temperature_2m,weather_code,wind_speed_10m,wind_direction_10m,cloud_cover,is_day,precipitation";
+
+/// The current weather at a point. Only data: the TUI, CLI and WASM each present it their own way.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Weather {
+    pub temperature: f64,
+    pub temperature_unit: String,
+    /// WMO weather interpretation code.
+    pub weather_code: u8,
+    pub wind_speed: f64,
+    pub wind_speed_unit: String,
+    /// Direction the wind blows from, in degrees.
+    pub wind_direction: f64,
+    /// Cloud cover in percent.
+    pub cloud_cover: f64,
+    pub is_day: bool,
+    pub precipitation: f64,
+    pub precipitation_unit: String,
+}
+
+impl Weather {
+    /// Parses the body of an Open-Meteo forecast requested with [`weather_url`].
+    pub fn from_open_meteo(body: &str) -> Result<Self, serde_json::Error> {
+        let response: OpenMeteoResponse = serde_json::from_str(body)?;
+        let (current, units) = (response.current, response.current_units);
+
+        Ok(Weather {
+            temperature: current.temperature_2m,
+            temperature_unit: units.temperature_2m,
+            weather_code: current.weather_code,
+            wind_speed: current.wind_speed_10m,
+            wind_speed_unit: units.wind_speed_10m,
+            wind_direction: current.wind_direction_10m,
+            cloud_cover: current.cloud_cover,
+            is_day: current.is_day != 0,
+            precipitation: current.precipitation,
+            precipitation_unit: units.precipitation,
+        })
+    }
+
+    pub fn code(&self) -> WmoCode {
+        WmoCode::from_code(self.weather_code)
+    }
+
+    pub fn description(&self) -> String {
+        self.code().description(self.is_day)
+    }
+
+    pub fn art(&self) -> WeatherArt {
+        self.code().art(self.is_day)
+    }
+}
+
+/// How strong rain, drizzle, snow or hail is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Intensity {
+    Light,
+    Moderate,
+    Heavy,
+}
+
+impl Intensity {
+    pub fn label(self) -> &'static str {
+        match self {
+            Intensity::Light => "Light",
+            Intensity::Moderate => "Moderate",
+            Intensity::Heavy => "Heavy",
+        }
+    }
+}
+
+/// WMO weather interpretation codes, as reported by Open-Meteo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WmoCode {
+    /// 0
+    ClearSky,
+    /// 1
+    MainlyClear,
+    /// 2
+    PartlyCloudy,
+    /// 3
+    Overcast,
+    /// 45
+    Fog,
+    /// 48
+    RimeFog,
+    /// 51, 53, 55
+    Drizzle(Intensity),
+    /// 56, 57
+    FreezingDrizzle(Intensity),
+    /// 61, 63, 65
+    Rain(Intensity),
+    /// 66, 67
+    FreezingRain(Intensity),
+    /// 71, 73, 75
+    Snowfall(Intensity),
+    /// 77
+    SnowGrains,
+    /// 80, 81, 82
+    RainShowers(Intensity),
+    /// 85, 86
+    SnowShowers(Intensity),
+    /// 95
+    Thunderstorm,
+    /// 96, 99
+    ThunderstormWithHail(Intensity),
+    /// Any code Open-Meteo does not document.
+    Unknown(u8),
+}
+
+impl WmoCode {
+    pub fn from_code(code: u8) -> Self {
+        use Intensity::*;
+        match code {
+            0 => WmoCode::ClearSky,
+            1 => WmoCode::MainlyClear,
+            2 => WmoCode::PartlyCloudy,
+            3 => WmoCode::Overcast,
+            45 => WmoCode::Fog,
+            48 => WmoCode::RimeFog,
+            51 => WmoCode::Drizzle(Light),
+            53 => WmoCode::Drizzle(Moderate),
+            55 => WmoCode::Drizzle(Heavy),
+            56 => WmoCode::FreezingDrizzle(Light),
+            57 => WmoCode::FreezingDrizzle(Heavy),
+            61 => WmoCode::Rain(Light),
+            63 => WmoCode::Rain(Moderate),
+            65 => WmoCode::Rain(Heavy),
+            66 => WmoCode::FreezingRain(Light),
+            67 => WmoCode::FreezingRain(Heavy),
+            71 => WmoCode::Snowfall(Light),
+            73 => WmoCode::Snowfall(Moderate),
+            75 => WmoCode::Snowfall(Heavy),
+            77 => WmoCode::SnowGrains,
+            80 => WmoCode::RainShowers(Light),
+            81 => WmoCode::RainShowers(Moderate),
+            82 => WmoCode::RainShowers(Heavy),
+            85 => WmoCode::SnowShowers(Light),
+            86 => WmoCode::SnowShowers(Heavy),
+            95 => WmoCode::Thunderstorm,
+            96 => WmoCode::ThunderstormWithHail(Light),
+            99 => WmoCode::ThunderstormWithHail(Heavy),
+            other => WmoCode::Unknown(other),
+        }
+    }
+
+    /// The numeric code this was parsed from.
+    pub fn code(self) -> u8 {
+        use Intensity::*;
+        match self {
+            WmoCode::ClearSky => 0,
+            WmoCode::MainlyClear => 1,
+            WmoCode::PartlyCloudy => 2,
+            WmoCode::Overcast => 3,
+            WmoCode::Fog => 45,
+            WmoCode::RimeFog => 48,
+            WmoCode::Drizzle(Light) => 51,
+            WmoCode::Drizzle(Moderate) => 53,
+            WmoCode::Drizzle(Heavy) => 55,
+            WmoCode::FreezingDrizzle(Light | Moderate) => 56,
+            WmoCode::FreezingDrizzle(Heavy) => 57,
+            WmoCode::Rain(Light) => 61,
+            WmoCode::Rain(Moderate) => 63,
+            WmoCode::Rain(Heavy) => 65,
+            WmoCode::FreezingRain(Light | Moderate) => 66,
+            WmoCode::FreezingRain(Heavy) => 67,
+            WmoCode::Snowfall(Light) => 71,
+            WmoCode::Snowfall(Moderate) => 73,
+            WmoCode::Snowfall(Heavy) => 75,
+            WmoCode::SnowGrains => 77,
+            WmoCode::RainShowers(Light) => 80,
+            WmoCode::RainShowers(Moderate) => 81,
+            WmoCode::RainShowers(Heavy) => 82,
+            WmoCode::SnowShowers(Light | Moderate) => 85,
+            WmoCode::SnowShowers(Heavy) => 86,
+            WmoCode::Thunderstorm => 95,
+            WmoCode::ThunderstormWithHail(Light | Moderate) => 96,
+            WmoCode::ThunderstormWithHail(Heavy) => 99,
+            WmoCode::Unknown(code) => code,
+        }
+    }
+
+    pub fn intensity(self) -> Option<Intensity> {
+        match self {
+            WmoCode::Drizzle(intensity)
+            | WmoCode::FreezingDrizzle(intensity)
+            | WmoCode::Rain(intensity)
+            | WmoCode::FreezingRain(intensity)
+            | WmoCode::Snowfall(intensity)
+            | WmoCode::RainShowers(intensity)
+            | WmoCode::SnowShowers(intensity)
+            | WmoCode::ThunderstormWithHail(intensity) => Some(intensity),
+            _ => None,
+        }
+    }
+
+    /// A short description, e.g. "Heavy rain showers" or "Clear night".
+    pub fn description(self, is_day: bool) -> String {
+        let with_intensity = |what: &str| {
+            let intensity = self.intensity().unwrap_or(Intensity::Moderate);
+            format!("{} {what}", intensity.label())
+        };
+        match self {
+            WmoCode::ClearSky if is_day => String::from("Clear sky"),
+            WmoCode::ClearSky => String::from("Clear night"),
+            WmoCode::MainlyClear => String::from("Mainly clear"),
+            WmoCode::PartlyCloudy => String::from("Partly cloudy"),
+            WmoCode::Overcast => String::from("Overcast"),
+            WmoCode::Fog => String::from("Fog"),
+            WmoCode::RimeFog => String::from("Depositing rime fog"),
+            WmoCode::Drizzle(_) => with_intensity("drizzle"),
+            WmoCode::FreezingDrizzle(_) => with_intensity("freezing drizzle"),
+            WmoCode::Rain(_) => with_intensity("rain"),
+            WmoCode::FreezingRain(_) => with_intensity("freezing rain"),
+            WmoCode::Snowfall(_) => with_intensity("snowfall"),
+            WmoCode::SnowGrains => String::from("Snow grains"),
+            WmoCode::RainShowers(_) => with_intensity("rain showers"),
+            WmoCode::SnowShowers(_) => with_intensity("snow showers"),
+            WmoCode::Thunderstorm => String::from("Thunderstorm"),
+            WmoCode::ThunderstormWithHail(intensity) => {
+                format!("Thunderstorm with {} hail", intensity.label().to_lowercase())
+            }
+            WmoCode::Unknown(code) => format!("Unknown weather code {code}"),
+        }
+    }
+
+    pub fn art(self, is_day: bool) -> WeatherArt {
+        match self {
+            WmoCode::ClearSky if is_day => WeatherArt::Sun,
+            WmoCode::ClearSky => WeatherArt::Moon,
+            WmoCode::MainlyClear | WmoCode::PartlyCloudy if is_day => WeatherArt::PartlyCloudyDay,
+            WmoCode::MainlyClear | WmoCode::PartlyCloudy => WeatherArt::PartlyCloudyNight,
+            WmoCode::Overcast => WeatherArt::Overcast,
+            WmoCode::Fog | WmoCode::RimeFog => WeatherArt::Fog,
+            WmoCode::Drizzle(_) => WeatherArt::Drizzle,
+            WmoCode::Rain(_) | WmoCode::RainShowers(_) => WeatherArt::Rain,
+            WmoCode::FreezingDrizzle(_) | WmoCode::FreezingRain(_) => WeatherArt::FreezingRain,
+            WmoCode::Snowfall(_) | WmoCode::SnowGrains | WmoCode::SnowShowers(_) => WeatherArt::Snow,
+            WmoCode::Thunderstorm | WmoCode::ThunderstormWithHail(_) => WeatherArt::Thunderstorm,
+            WmoCode::Unknown(_) => WeatherArt::OuterSpace,
+        }
+    }
+}
+
+/// ASCII art for a kind of weather. Each front end picks its own colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeatherArt {
+    Sun,
+    Moon,
+    PartlyCloudyDay,
+    PartlyCloudyNight,
+    Overcast,
+    Fog,
+    Drizzle,
+    Rain,
+    FreezingRain,
+    Snow,
+    Thunderstorm,
+    /// For codes without art of their own.
+    OuterSpace,
+}
+
+impl WeatherArt {
+    pub fn lines(self) -> &'static [&'static str] {
+        match self {
+            WeatherArt::Sun => &[
+                r"    \   /    ",
+                r"     .-.     ",
+                r"  - (   ) -  ",
+                r"     `-'     ",
+                r"    /   \    ",
+            ],
+            WeatherArt::Moon => &[
+                r"    .--.   * ",
+                r"   /  .'     ",
+                r" *|  (    *  ",
+                r"   \  '.     ",
+                r"    '--'  *  ",
+            ],
+            WeatherArt::PartlyCloudyDay => &[
+                r"   \  /      ",
+                r" _ /''.-.    ",
+                r"   \_(   ).  ",
+                r"   /(___(__) ",
+                r"             ",
+            ],
+            WeatherArt::PartlyCloudyNight => &[
+                r"   .--.  *   ",
+                r"  /  .-.     ",
+                r" |  (   ).   ",
+                r"  \(___(__)  ",
+                r"   '--'   *  ",
+            ],
+            WeatherArt::Overcast => &[
+                r"             ",
+                r"     .--.    ",
+                r"  .-(    ).  ",
+                r" (___.__)__) ",
+                r"             ",
+            ],
+            WeatherArt::Fog => &[
+                r"             ",
+                r" _ - _ - _ - ",
+                r"  _ - _ - _  ",
+                r" _ - _ - _ - ",
+                r"             ",
+            ],
+            WeatherArt::Drizzle => &[
+                r"     .-.     ",
+                r"    (   ).   ",
+                r"   (___(__)  ",
+                r"    ' ' ' '  ",
+                r"   ' ' ' '   ",
+            ],
+            WeatherArt::Rain => &[
+                r"     .-.     ",
+                r"    (   ).   ",
+                r"   (___(__)  ",
+                r"   / / / /   ",
+                r"  / / / /    ",
+            ],
+            WeatherArt::FreezingRain => &[
+                r"     .-.     ",
+                r"    (   ).   ",
+                r"   (___(__)  ",
+                r"   / * / *   ",
+                r"  * / * /    ",
+            ],
+            WeatherArt::Snow => &[
+                r"     .-.     ",
+                r"    (   ).   ",
+                r"   (___(__)  ",
+                r"   *  *  *   ",
+                r"  *  *  *    ",
+            ],
+            WeatherArt::Thunderstorm => &[
+                r"     .-.     ",
+                r"    (   ).   ",
+                r"   (___(__)  ",
+                r"    /_ /_    ",
+                r"     /  /    ",
+            ],
+            WeatherArt::OuterSpace => &[
+                r" *       *   ",
+                r"      *      ",
+                r"   *      *  ",
+                r"             ",
+                r"  *    *     ",
+            ],
+        }
+    }
+}
+
+/// Units Open-Meteo reports the weather in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WeatherUnits {
+    pub temperature: TemperatureUnit,
+    pub wind_speed: WindSpeedUnit,
+    pub precipitation: PrecipitationUnit,
+}
+
+impl WeatherUnits {
+    /// The Open-Meteo query parameters selecting these units.
+    fn query(&self) -> String {
+        format!(
+            "temperature_unit={}&wind_speed_unit={}&precipitation_unit={}",
+            self.temperature.as_str(),
+            self.wind_speed.as_str(),
+            self.precipitation.as_str()
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+}
+
+impl TemperatureUnit {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TemperatureUnit::Celsius => "celsius",
+            TemperatureUnit::Fahrenheit => "fahrenheit",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WindSpeedUnit {
+    #[default]
+    Kmh,
+    Ms,
+    Mph,
+    /// Knots.
+    Kn,
+}
+
+impl WindSpeedUnit {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WindSpeedUnit::Kmh => "kmh",
+            WindSpeedUnit::Ms => "ms",
+            WindSpeedUnit::Mph => "mph",
+            WindSpeedUnit::Kn => "kn",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PrecipitationUnit {
+    #[default]
+    Mm,
+    Inch,
+}
+
+impl PrecipitationUnit {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PrecipitationUnit::Mm => "mm",
+            PrecipitationUnit::Inch => "inch",
+        }
+    }
+}
+
+/// URL of the current weather at `lat`, `lon` on the Open-Meteo server at `base`, with every
+/// field [`Weather`] needs in `units`.
+pub fn weather_url(base: &str, lat: f64, lon: f64, units: &WeatherUnits) -> String {
+    format!(
+        "{base}?latitude={lat}&longitude={lon}&current={CURRENT_VARIABLES}&{}",
+        units.query()
+    )
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenMeteoResponse {
+    current: Current,
+    current_units: CurrentUnits,
+}
+
+#[derive(Debug, Deserialize)]
+struct Current {
+    temperature_2m: f64,
+    weather_code: u8,
+    wind_speed_10m: f64,
+    wind_direction_10m: f64,
+    cloud_cover: f64,
+    is_day: u8,
+    precipitation: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct CurrentUnits {
+    temperature_2m: String,
+    wind_speed_10m: String,
+    precipitation: String,
+}
@@ -0,0 +1,304 @@
+use crate::error::IssError;
+use crate::http::HttpClient;
+use crate::iss::{get_crew, get_weather, OPEN_NOTIFY_CREW_URL};
+use crate::oem::OemEphemeris;
+use crate::orbit::Propagator;
+use crate::provider::{fetch_with_fallback, Position, PositionProvider, PositionSource};
+use crate::tle::{fetch_tle, Tle};
+use crate::weather::{Weather, WeatherUnits, OPEN_METEO_URL};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::runtime::Runtime;
+use tokio::sync::{mpsc, watch};
+use tokio::time::{interval, MissedTickBehavior};
+
+/// Data fetched in the background, sent to whoever owns the [`Worker`].
+#[derive(Debug)]
+pub enum Update {
+    Position(Result<(Position, PositionSource), IssError>),
+    Crew(Result<Vec<String>, IssError>),
+    Weather(Result<Weather, IssError>),
+    /// The elements of a satellite asked for with [`Worker::fetch_tle`], by NORAD ID.
+    Tle(u32, Result<Tle, IssError>),
+    /// The ISS's own elements, asked for with [`Worker::fetch_iss_tle`].
+    IssTle(Result<Tle, IssError>),
+    /// The ISS's OEM asked for with [`Worker::fetch_oem`], with its state vectors as text.
+    Oem(Result<(OemEphemeris, String), IssError>),
+}
+
+/// Where positions are fetched from, and the propagator they fall back to.
+type Sources = (Box<dyn PositionProvider>, Option<Arc<dyn Propagator>>);
+
+/// How often each kind of data is fetched.
+#[derive(Debug, Clone, Copy)]
+pub struct Schedule {
+    pub position: Duration,
+    pub crew: Duration,
+    pub weather: Duration,
+}
+
+/// Where the crew and weather are fetched from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Endpoints {
+    pub crew: String,
+    pub weather: String,
+    pub units: WeatherUnits,
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Endpoints {
+            crew: OPEN_NOTIFY_CREW_URL.to_string(),
+            weather: OPEN_METEO_URL.to_string(),
+            units: WeatherUnits::default(),
+        }
+    }
+}
+
+/// Fetches positions, crew and weather on a tokio runtime so callers never block on HTTP.
+/// Every kind of data is fetched once straight away and then on its [`Schedule`].
+pub struct Worker {
+    runtime: Option<Runtime>,
+    refresh: mpsc::UnboundedSender<()>,
+    sources: mpsc::UnboundedSender<Sources>,
+    location: watch::Sender<Option<(f64, f64)>>,
+    client: Arc<dyn HttpClient>,
+    sender: mpsc::UnboundedSender<Update>,
+    updates: mpsc::UnboundedReceiver<Update>,
+}
+
+impl Worker {
+    /// Starts the worker. Without a `provider` no positions are fetched (e.g. during a replay),
+    /// and weather waits until a location is given with [`Worker::set_location`].
+    pub fn spawn(
+        provider: Option<Box<dyn PositionProvider>>,
+        propagator: Option<Arc<dyn Propagator>>,
+        schedule: Schedule,
+        endpoints: Endpoints,
+        client: Arc<dyn HttpClient>,
+    ) -> Result<Self, IssError> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()?;
+        let (refresh, refresh_requests) = mpsc::unbounded_channel();
+        let (sources, source_changes) = mpsc::unbounded_channel();
+        let (location, location_changes) = watch::channel(None);
+        let (sender, updates) = mpsc::unbounded_channel();
+
+        if let Some(provider) = provider {
+            runtime.spawn(fetch_positions(
+                provider,
+                propagator,
+                schedule.position,
+                refresh_requests,
+                source_changes,
+                sender.clone(),
+            ));
+        }
+        runtime.spawn(fetch_crew(
+            schedule.crew,
+            client.clone(),
+            endpoints.crew,
+            sender.clone(),
+        ));
+        runtime.spawn(fetch_weather(
+            schedule.weather,
+            client.clone(),
+            endpoints.weather,
+            endpoints.units,
+            location_changes,
+            sender.clone(),
+        ));
+
+        Ok(Worker {
+            runtime: Some(runtime),
+            refresh,
+            sources,
+            location,
+            client,
+            sender,
+            updates,
+        })
+    }
+
+    /// Fetches a position now instead of waiting for the next scheduled one.
+    pub fn refresh_position(&self) {
+        let _ = self.refresh.send(());
+    }
+
+    /// Fetches positions from `provider`, falling back to `propagator`, from now on, starting
+    /// with one straight away. Does nothing when the worker was started without a provider.
+    pub fn set_provider(&self, provider: Box<dyn PositionProvider>, propagator: Option<Arc<dyn Propagator>>) {
+        let _ = self.sources.send((provider, propagator));
+    }
+
+    /// Where the weather should be reported for from now on.
+    pub fn set_location(&self, lat: f64, lon: f64) {
+        self.location.send_replace(Some((lat, lon)));
+    }
+
+    /// Downloads the TLE of `norad_id` from `url` once; it arrives as [`Update::Tle`].
+    pub fn fetch_tle(&self, norad_id: u32, url: String) {
+        let Some(runtime) = &self.runtime else {
+            return;
+        };
+        let (client, sender) = (self.client.clone(), self.sender.clone());
+        runtime.spawn(async move {
+            let tle = blocking(move || fetch_tle(client.as_ref(), &url)).await;
+            let _ = sender.send(Update::Tle(norad_id, tle));
+        });
+    }
+
+    /// Downloads the ISS's TLE from `url` once; it arrives as [`Update::IssTle`].
+    pub fn fetch_iss_tle(&self, url: String) {
+        let Some(runtime) = &self.runtime else {
+            return;
+        };
+        let (client, sender) = (self.client.clone(), self.sender.clone());
+        runtime.spawn(async move {
+            let tle = blocking(move || fetch_tle(client.as_ref(), &url)).await;
+            let _ = sender.send(Update::IssTle(tle));
+        });
+    }
+
+    /// Downloads and parses the OEM at `url` once; it arrives as [`Update::Oem`].
+    pub fn fetch_oem(&self, url: String) {
+        let Some(runtime) = &self.runtime else {
+            return;
+        };
+        let (client, sender) = (self.client.clone(), self.sender.clone());
+        runtime.spawn(async move {
+            let oem = blocking(move || {
+                let content = client.get(&url)?;
+                let ephemeris = OemEphemeris::parse(&content);
+                if ephemeris.is_empty() {
+                    return Err(IssError::Propagation(format!("no state vectors in the OEM from {url}")));
+                }
+                let coordinates = OrbitalEphemerisMessage::construct_oem(&content).coordinates;
+                Ok((ephemeris, coordinates))
+            })
+            .await;
+            let _ = sender.send(Update::Oem(oem));
+        });
+    }
+
+    /// The next update that has arrived, without waiting.
+    pub fn try_recv(&mut self) -> Option<Update> {
+        self.updates.try_recv().ok()
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        // Requests still in flight are abandoned rather than waited for.
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+/// Runs a blocking fetch on tokio's blocking thread pool.
+async fn blocking<T, F>(fetch: F) -> Result<T, IssError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, IssError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(fetch)
+        .await
+        .unwrap_or_else(|err| Err(IssError::Io(std::io::Error::other(err))))
+}
+
+async fn fetch_positions(
+    provider: Box<dyn PositionProvider>,
+    mut propagator: Option<Arc<dyn Propagator>>,
+    period: Duration,
+    mut refresh_requests: mpsc::UnboundedReceiver<()>,
+    mut source_changes: mpsc::UnboundedReceiver<Sources>,
+    sender: mpsc::UnboundedSender<Update>,
+) {
+    let provider = Arc::new(Mutex::new(provider));
+    let mut timer = interval(period);
+    timer.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            _ = timer.tick() => {}
+            request = refresh_requests.recv() => {
+                if request.is_none() {
+                    return;
+                }
+                timer.reset();
+            }
+            sources = source_changes.recv() => {
+                let Some((new_provider, new_propagator)) = sources else {
+                    return;
+                };
+                *provider.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = new_provider;
+                propagator = new_propagator;
+                timer.reset();
+            }
+        }
+
+        let provider = provider.clone();
+        let propagator = propagator.clone();
+        let result = blocking(move || {
+            let mut provider = provider.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            fetch_with_fallback(provider.as_mut(), propagator.as_deref())
+        })
+        .await;
+        if sender.send(Update::Position(result)).is_err() {
+            return;
+        }
+    }
+}
+
+async fn fetch_crew(
+    period: Duration,
+    client: Arc<dyn HttpClient>,
+    url: String,
+    sender: mpsc::UnboundedSender<Update>,
+) {
+    let mut timer = interval(period);
+    timer.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        timer.tick().await;
+        let (client, url) = (client.clone(), url.clone());
+        let crew = blocking(move || get_crew(client.as_ref(), &url)).await;
+        if sender.send(Update::Crew(crew)).is_err() {
+            return;
+        }
+    }
+}
+
+async fn fetch_weather(
+    period: Duration,
+    client: Arc<dyn HttpClient>,
+    url: String,
+    units: WeatherUnits,
+    mut location_changes: watch::Receiver<Option<(f64, f64)>>,
+    sender: mpsc::UnboundedSender<Update>,
+) {
+    let mut timer = interval(period);
+    timer.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        timer.tick().await;
+        let location = loop {
+            if let Some(location) = *location_changes.borrow_and_update() {
+                break location;
+            }
+            if location_changes.changed().await.is_err() {
+                return;
+            }
+        };
+
+        let (lat, lon) = location;
+        let (client, url) = (client.clone(), url.clone());
+        let weather = blocking(move || get_weather(client.as_ref(), &url, lat, lon, &units)).await;
+        if sender.send(Update::Weather(weather)).is_err() {
+            return;
+        }
+    }
+}
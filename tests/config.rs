@@ -0,0 +1,122 @@
+//! The configuration file: its defaults, what is rejected, and the command line overriding it.
+//! Command line handling lives in the binary, so those tests run `rs_iss` itself, offline.
+
+use iss_ratatui::config::{Config, DEFAULT_CONFIG};
+use iss_ratatui::error::IssError;
+use serde_json::Value;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+/// A fresh path for `name` under the target directory.
+fn temp_path(name: &str) -> PathBuf {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("config").join(name);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn write_config(name: &str, text: &str) -> PathBuf {
+    let path = temp_path(name);
+    std::fs::write(&path, text).unwrap();
+    path
+}
+
+fn rs_iss(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rs_iss"))
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn default_file_holds_the_defaults() {
+    let path = write_config("default.toml", DEFAULT_CONFIG);
+    assert_eq!(Config::read(&path).unwrap(), Config::default());
+}
+
+#[test]
+fn config_init_writes_a_file_that_parses() {
+    let path = temp_path("init/config.toml");
+    let init = rs_iss(&["config", "init", "--config", path.to_str().unwrap()]);
+    assert!(init.status.success(), "{}", String::from_utf8_lossy(&init.stderr));
+    assert_eq!(Config::read(&path).unwrap(), Config::default());
+
+    // An existing file is only replaced with --force.
+    assert!(!rs_iss(&["config", "init", "--config", path.to_str().unwrap()]).status.success());
+    assert!(rs_iss(&["config", "init", "--force", "--config", path.to_str().unwrap()]).status.success());
+}
+
+#[test]
+fn invalid_values_are_rejected() {
+    for (name, text, message) in [
+        ("refresh.toml", "[intervals]\nrefresh_ms = 0\n", "intervals.refresh_ms must be greater than 0"),
+        ("poll.toml", "[intervals]\npoll_ms = 0\n", "intervals.poll_ms must be greater than 0"),
+        ("crew.toml", "[intervals]\ncrew_minutes = 0\n", "intervals.crew_minutes must be greater than 0"),
+        ("weather.toml", "[intervals]\nweather_minutes = 0\n", "intervals.weather_minutes must be greater than 0"),
+        ("overflow.toml", "[intervals]\ncrew_minutes = 307445734561825861\n", "intervals.crew_minutes"),
+        ("no_orbits.toml", "[ui]\norbits = 0.0\n", "ui.orbits must be greater than 0, not 0"),
+        ("negative_orbits.toml", "[ui]\norbits = -2.0\n", "ui.orbits must be greater than 0, not -2"),
+        ("nan_orbits.toml", "[ui]\norbits = nan\n", "ui.orbits must be greater than 0, not NaN"),
+        ("many_orbits.toml", "[ui]\norbits = 1e9\n", "ui.orbits = 1000000000 is more than 16"),
+        ("keys.toml", "[keys]\nexport = \"q\"\n", "keys.quit and keys.export are both bound to 'q'"),
+        ("unknown.toml", "[intervals]\nrefresh = 5\n", "unknown field"),
+    ] {
+        let path = write_config(name, text);
+        match Config::read(&path) {
+            Err(IssError::InvalidConfig(msg)) => {
+                assert!(msg.starts_with(&path.display().to_string()), "{msg}");
+                assert!(msg.contains(message), "{msg}");
+            }
+            other => panic!("{name}: {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn the_binary_refuses_an_invalid_file() {
+    let path = write_config("binary.toml", "[intervals]\npoll_ms = 0\n");
+    let output = rs_iss(&["passes", "--config", path.to_str().unwrap(), "--tle", "tests/fixtures/iss.tle"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("intervals.poll_ms"));
+}
+
+#[test]
+fn the_binary_refuses_invalid_orbits() {
+    for orbits in ["0", "1e6"] {
+        let output = rs_iss(&["passes", "--tle", "tests/fixtures/iss.tle", "--orbits", orbits]);
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("ui.orbits"), "{orbits}");
+    }
+}
+
+#[test]
+fn command_line_overrides_the_file() {
+    let path = write_config(
+        "observer.toml",
+        "[observer]\nname = \"From the file\"\nlat = 10.0\nlon = 20.0\nelevation = 5.0\n",
+    );
+    let output = rs_iss(&[
+        "passes",
+        "--config",
+        path.to_str().unwrap(),
+        "--tle",
+        "tests/fixtures/iss.tle",
+        "--lat",
+        "52.2053",
+        "--observer-name",
+        "Cambridge",
+        "--hours",
+        "1",
+        "--format",
+        "json",
+    ]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    // What is not given on the command line still comes from the file.
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["observer"]["name"], "Cambridge");
+    assert_eq!(report["observer"]["lat"], 52.2053);
+    assert_eq!(report["observer"]["lon"], 20.0);
+    assert_eq!(report["observer"]["elevation"], 5.0);
+}
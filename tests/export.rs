@@ -0,0 +1,167 @@
+//! Ground tracks exported as GeoJSON, KML and GPX.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use iss_ratatui::export::{ExportFormat, Tracks};
+use iss_ratatui::provider::Position;
+use serde_json::{json, Value};
+use std::path::Path;
+
+fn start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, 17, 13, 0, 0).unwrap()
+}
+
+fn position(minutes: i64, lat: f64, lon: f64) -> Position {
+    Position {
+        lat,
+        lon,
+        alt: 415.0,
+        timestamp: (start() + Duration::minutes(minutes)).timestamp() as f64,
+    }
+}
+
+/// A flown track that crosses the antimeridian between its second and third positions,
+/// and a predicted one that does not.
+fn tracks() -> Tracks {
+    Tracks {
+        past: vec![
+            position(0, 10.0, 170.0),
+            position(1, 12.0, 178.0),
+            position(2, 14.0, -176.0),
+            position(3, 16.0, -168.0),
+        ],
+        predicted: vec![position(3, 16.0, -168.0), position(4, 18.0, -160.0)],
+    }
+}
+
+/// Every `<tag>…</tag>` value in `text`, in order.
+fn values<'a>(text: &'a str, tag: &str) -> Vec<&'a str> {
+    let (open, close) = (format!("<{tag}>"), format!("</{tag}>"));
+    text.split(open.as_str())
+        .skip(1)
+        .map(|rest| rest.split(close.as_str()).next().unwrap())
+        .collect()
+}
+
+#[test]
+fn formats_come_from_names_and_extensions() {
+    assert_eq!("GeoJSON".parse(), Ok(ExportFormat::GeoJson));
+    assert_eq!("json".parse(), Ok(ExportFormat::GeoJson));
+    assert!("shp".parse::<ExportFormat>().unwrap_err().contains("expected one of: geojson, kml, gpx"));
+    for format in ExportFormat::ALL {
+        let path = format!("track.{}", format.extension());
+        assert_eq!(ExportFormat::from_path(Path::new(&path)), Some(format));
+    }
+    assert_eq!(ExportFormat::from_path(Path::new("track")), None);
+}
+
+#[test]
+fn geojson_splits_at_the_antimeridian() {
+    let geojson: Value = serde_json::from_str(&tracks().to_geojson()).unwrap();
+    assert_eq!(geojson["type"], "FeatureCollection");
+    let features = geojson["features"].as_array().unwrap();
+    assert_eq!(features.len(), 3);
+
+    // The crossing is a third of the way from 178° to 184° (-176°), at 12⅔°N.
+    let crossing = 12.0 + 2.0 / 3.0;
+    let coordinates = |feature: &Value| -> Vec<(f64, f64)> {
+        let points = feature["geometry"]["coordinates"].as_array().unwrap();
+        points.iter().map(|point| (point[0].as_f64().unwrap(), point[1].as_f64().unwrap())).collect()
+    };
+    let west = coordinates(&features[0]);
+    let east = coordinates(&features[1]);
+    assert_eq!(west[..2], [(170.0, 10.0), (178.0, 12.0)]);
+    assert_eq!(west[2].0, 180.0);
+    assert!((west[2].1 - crossing).abs() < 1e-9);
+    assert_eq!(east[0].0, -180.0);
+    assert!((east[0].1 - crossing).abs() < 1e-9);
+    assert_eq!(east[1..], [(-176.0, 14.0), (-168.0, 16.0)]);
+    assert_eq!(features[1]["geometry"]["type"], "LineString");
+}
+
+#[test]
+fn geojson_segments_are_timed_by_their_own_positions() {
+    let geojson: Value = serde_json::from_str(&tracks().to_geojson()).unwrap();
+    let properties: Vec<&Value> = geojson["features"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|feature| &feature["properties"])
+        .collect();
+
+    assert_eq!(
+        properties,
+        [
+            &json!({ "name": "Past track", "start": "2026-10-17T13:00:00Z", "end": "2026-10-17T13:01:00Z" }),
+            &json!({ "name": "Past track", "start": "2026-10-17T13:02:00Z", "end": "2026-10-17T13:03:00Z" }),
+            &json!({ "name": "Predicted track", "start": "2026-10-17T13:03:00Z", "end": "2026-10-17T13:04:00Z" }),
+        ]
+    );
+}
+
+#[test]
+fn kml_tracks_have_a_time_for_every_coordinate() {
+    let kml = tracks().to_kml();
+    assert!(kml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<kml "));
+    assert!(kml.ends_with("</Document>\n</kml>\n"));
+    assert_eq!(values(&kml, "name"), ["ISS ground track", "Past track", "Predicted track"]);
+
+    let tracks: Vec<&str> = values(&kml, "gx:Track");
+    assert_eq!(tracks.len(), 2);
+    assert_eq!(
+        values(tracks[0], "when"),
+        ["2026-10-17T13:00:00Z", "2026-10-17T13:01:00Z", "2026-10-17T13:02:00Z", "2026-10-17T13:03:00Z"]
+    );
+    assert_eq!(
+        values(tracks[0], "gx:coord"),
+        ["170 10 415000", "178 12 415000", "-176 14 415000", "-168 16 415000"]
+    );
+    assert_eq!(values(tracks[1], "when"), ["2026-10-17T13:03:00Z", "2026-10-17T13:04:00Z"]);
+    assert_eq!(values(tracks[1], "altitudeMode"), ["absolute"]);
+}
+
+#[test]
+fn gpx_has_a_track_per_name_and_a_segment_per_side_of_the_antimeridian() {
+    let gpx = tracks().to_gpx();
+    assert!(gpx.starts_with(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<gpx version=\"1.1\" creator=\"rs_iss\" xmlns=\"http://www.topografix.com/GPX/1/1\">\n"
+    ));
+    assert!(gpx.ends_with("</gpx>\n"));
+    for tag in ["trk", "trkseg", "trkpt", "ele", "time", "name"] {
+        assert_eq!(
+            gpx.matches(&format!("<{tag}>")).count() + gpx.matches(&format!("<{tag} ")).count(),
+            gpx.matches(&format!("</{tag}>")).count(),
+            "unbalanced <{tag}> in\n{gpx}"
+        );
+    }
+
+    let tracks = values(&gpx, "trk");
+    assert_eq!(tracks.len(), 2);
+    assert_eq!(values(tracks[0], "name"), ["Past track"]);
+    let segments = values(tracks[0], "trkseg");
+    assert_eq!(segments.len(), 2);
+    assert_eq!(
+        segments[0].lines().filter(|line| !line.is_empty()).collect::<Vec<_>>(),
+        [
+            "<trkpt lat=\"10\" lon=\"170\"><ele>415000</ele><time>2026-10-17T13:00:00Z</time></trkpt>",
+            "<trkpt lat=\"12\" lon=\"178\"><ele>415000</ele><time>2026-10-17T13:01:00Z</time></trkpt>",
+        ]
+    );
+    assert_eq!(values(segments[1], "time"), ["2026-10-17T13:02:00Z", "2026-10-17T13:03:00Z"]);
+    assert_eq!(values(tracks[1], "name"), ["Predicted track"]);
+    assert_eq!(values(tracks[1], "trkseg").len(), 1);
+}
+
+#[test]
+fn empty_tracks_are_left_out() {
+    let only_predicted = Tracks {
+        past: Vec::new(),
+        ..tracks()
+    };
+    assert!(!only_predicted.is_empty());
+    assert!(Tracks::default().is_empty());
+
+    let geojson: Value = serde_json::from_str(&only_predicted.to_geojson()).unwrap();
+    assert_eq!(geojson["features"].as_array().unwrap().len(), 1);
+    assert!(!only_predicted.to_kml().contains("Past track"));
+    assert_eq!(Tracks::default().to_gpx().matches("<trk>").count(), 0);
+}
@@ -0,0 +1,118 @@
+//! The area the ISS can be seen or reached from, and the countries inside it.
+
+use iss_ratatui::config::Config;
+use iss_ratatui::footprint::{bearing, central_angle, destination, Footprint};
+use iss_ratatui::iss::Iss;
+use iss_ratatui::provider::{Position, PositionSource};
+use iss_ratatui::ui::App;
+
+#[test]
+fn horizon_radius_at_station_altitude() {
+    let horizon = Footprint::new(0.0, 0.0, 420.0, 0.0);
+
+    // acos(R / (R + h)) for a spherical Earth.
+    assert!((horizon.radius - 20.256).abs() < 0.001, "{}", horizon.radius);
+    assert!((horizon.radius_km() - 2252.4).abs() < 0.1, "{}", horizon.radius_km());
+}
+
+#[test]
+fn minimum_elevation_shrinks_the_footprint() {
+    let horizon = Footprint::new(0.0, 0.0, 420.0, 0.0);
+    let reachable = Footprint::new(0.0, 0.0, 420.0, 10.0);
+
+    assert!(reachable.radius < horizon.radius);
+    assert!((reachable.radius - 12.497).abs() < 0.001, "{}", reachable.radius);
+    assert_eq!(Footprint::new(0.0, 0.0, 420.0, 90.0).radius, 0.0);
+}
+
+#[test]
+fn outline_is_at_the_radius_from_the_centre() {
+    let footprint = Footprint::new(51.5, -0.1, 420.0, 10.0);
+
+    for segment in footprint.outline() {
+        for (lon, lat) in segment {
+            let angle = central_angle(51.5, -0.1, lat, lon);
+            assert!((angle - footprint.radius).abs() < 1e-6, "{angle}");
+        }
+    }
+}
+
+#[test]
+fn outline_is_split_at_the_antimeridian() {
+    let footprint = Footprint::new(0.0, 175.0, 420.0, 0.0);
+    let outline = footprint.outline();
+
+    assert!(outline.len() >= 2, "{} segments", outline.len());
+    for (lon, _) in outline.iter().flatten() {
+        assert!((-180.0..=180.0).contains(lon), "{lon}");
+    }
+}
+
+#[test]
+fn sample_points_lie_inside() {
+    let footprint = Footprint::new(-33.9, 18.4, 420.0, 0.0);
+    let points = footprint.sample_points();
+
+    assert!(points.len() > 100, "{}", points.len());
+    for (lat, lon) in points {
+        assert!(central_angle(-33.9, 18.4, lat, lon) <= footprint.radius + 1e-6);
+    }
+    assert!(footprint.contains(-26.2, 28.0), "Johannesburg");
+    assert!(!footprint.contains(51.5, -0.1), "London");
+}
+
+#[test]
+fn countries_are_sorted_and_listed_once() {
+    let footprint = Footprint::new(0.0, 0.0, 420.0, 0.0);
+    let hemisphere = |lat: f64, _lon: f64| match lat {
+        lat if lat > 5.0 => Some(String::from("North")),
+        lat if lat < -5.0 => Some(String::from("South")),
+        _ => None,
+    };
+
+    assert_eq!(footprint.countries(hemisphere), ["North", "South"]);
+}
+
+/// The countries the tracker lists with the ISS over `lat`, `lon`.
+fn reachable_from(lat: f64, lon: f64) -> Vec<String> {
+    let mut iss = Iss::new();
+    iss.set_position(
+        Position {
+            lat,
+            lon,
+            alt: 420.0,
+            timestamp: 1_792_242_000.0,
+        },
+        PositionSource::Live,
+    );
+    let mut app = App::new(Config::default()).unwrap();
+    app.refresh_tracks(&iss);
+    app.footprint_countries
+}
+
+#[test]
+fn only_countries_inside_the_footprint_are_listed() {
+    // Open water samples must not be matched to the nearest land far outside the circle.
+    assert!(reachable_from(-45.0, -120.0).is_empty());
+    assert!(reachable_from(30.0, -40.0).is_empty());
+
+    let central_europe = reachable_from(50.0, 10.0);
+    assert!(central_europe.contains(&String::from("Germany")), "{central_europe:?}");
+    assert!(!central_europe.contains(&String::from("Egypt")), "{central_europe:?}");
+}
+
+#[test]
+fn destination_wraps_the_longitude() {
+    let (lat, lon) = destination(0.0, 179.0, 90.0, 2.0);
+
+    assert!(lat.abs() < 1e-9);
+    assert!((lon + 179.0).abs() < 1e-9, "{lon}");
+}
+
+#[test]
+fn bearing_points_along_the_great_circle() {
+    assert!((bearing(0.0, 0.0, 0.0, 10.0) - 90.0).abs() < 1e-9);
+    assert!(bearing(0.0, 0.0, 10.0, 0.0).abs() < 1e-9);
+    assert!((bearing(0.0, 0.0, 0.0, -10.0) - 270.0).abs() < 1e-9);
+    assert!((bearing(0.0, 179.0, 0.0, -179.0) - 90.0).abs() < 1e-9);
+}
@@ -0,0 +1,249 @@
+//! The API clients against a local stand-in server replaying recorded responses.
+
+use iss_ratatui::error::IssError;
+use iss_ratatui::http::{HttpClient, ReqwestClient};
+use iss_ratatui::iss::{get_crew, get_position, get_weather};
+use iss_ratatui::provider::{
+    fetch_with_fallback, OpenNotify, PositionProvider, PositionSource, WhereTheIss, DEFAULT_ALTITUDE_KM,
+};
+use iss_ratatui::tle::{fetch_tle, Sgp4, Tle};
+use iss_ratatui::weather::{TemperatureUnit, WeatherUnits, WindSpeedUnit, WmoCode};
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+const WHERE_THE_ISS: &str = include_str!("fixtures/wheretheiss.json");
+const WHERE_THE_ISS_TRUNCATED: &str = include_str!("fixtures/wheretheiss_truncated.json");
+const WHERE_THE_ISS_MISSING_ALTITUDE: &str = include_str!("fixtures/wheretheiss_missing_altitude.json");
+const OPEN_NOTIFY_ISS_NOW: &str = include_str!("fixtures/open_notify_iss_now.json");
+const OPEN_NOTIFY_ASTROS: &str = include_str!("fixtures/open_notify_astros.json");
+const OPEN_NOTIFY_ASTROS_NO_PEOPLE: &str = include_str!("fixtures/open_notify_astros_no_people.json");
+const OPEN_METEO: &str = include_str!("fixtures/open_meteo.json");
+const OPEN_METEO_MISSING_CURRENT: &str = include_str!("fixtures/open_meteo_missing_current.json");
+const ISS_TLE: &str = include_str!("fixtures/iss.tle");
+
+/// Client timeout; the stand-in server hangs for longer than this to provoke a timeout.
+const TIMEOUT: Duration = Duration::from_millis(300);
+
+/// What the stand-in server answers one request with.
+enum Reply {
+    Body(&'static str),
+    Status(u16),
+    /// Accepts the request and never answers.
+    Hang,
+}
+
+/// A local server answering one request per reply, in order. The paths it was asked for arrive
+/// on `requests`.
+struct StandIn {
+    url: String,
+    requests: Receiver<String>,
+}
+
+impl StandIn {
+    fn start(replies: Vec<Reply>) -> StandIn {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, requests) = mpsc::channel();
+
+        thread::spawn(move || {
+            for reply in replies {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                // Skip the headers; none of the clients send a body.
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+                let path = request_line.split_whitespace().nth(1).unwrap_or_default().to_string();
+                let _ = sender.send(path);
+
+                let (status, body) = match reply {
+                    Reply::Body(body) => (200, body),
+                    Reply::Status(status) => (status, "{\"error\": \"stand-in\"}"),
+                    Reply::Hang => {
+                        thread::sleep(TIMEOUT * 5);
+                        continue;
+                    }
+                };
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {status} Stand-in\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+            }
+        });
+
+        StandIn { url, requests }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.url)
+    }
+
+    fn requested(&self) -> String {
+        self.requests.recv_timeout(Duration::from_secs(5)).unwrap()
+    }
+}
+
+fn client() -> Arc<dyn HttpClient> {
+    Arc::new(ReqwestClient::new(TIMEOUT).unwrap())
+}
+
+#[test]
+fn where_the_iss_position() {
+    let server = StandIn::start(vec![Reply::Body(WHERE_THE_ISS)]);
+    let position = get_position(client().as_ref(), &server.url("/v1/satellites/25544")).unwrap();
+
+    assert_eq!(server.requested(), "/v1/satellites/25544");
+    assert_eq!(position.lat, 50.11496269845);
+    assert_eq!(position.lon, 118.07900427317);
+    assert_eq!(position.alt, 408.05526028199);
+    assert_eq!(position.timestamp, 1364069476.0);
+}
+
+#[test]
+fn where_the_iss_provider_uses_its_client() {
+    let server = StandIn::start(vec![Reply::Body(WHERE_THE_ISS)]);
+    let mut provider = WhereTheIss {
+        url: server.url("/v1/satellites/25544"),
+        client: client(),
+    };
+
+    assert_eq!(provider.fetch_position().unwrap().lat, 50.11496269845);
+    assert_eq!(provider.source(), PositionSource::Live);
+}
+
+#[test]
+fn open_notify_position_has_string_coordinates_and_no_altitude() {
+    let server = StandIn::start(vec![Reply::Body(OPEN_NOTIFY_ISS_NOW)]);
+    let mut provider = OpenNotify {
+        url: server.url("/iss-now.json"),
+        client: client(),
+    };
+    let position = provider.fetch_position().unwrap();
+
+    assert_eq!(position.lat, -47.3635);
+    assert_eq!(position.lon, -120.9013);
+    assert_eq!(position.alt, DEFAULT_ALTITUDE_KM);
+    assert_eq!(position.timestamp, 1364795862.0);
+}
+
+#[test]
+fn crew_names() {
+    let server = StandIn::start(vec![Reply::Body(OPEN_NOTIFY_ASTROS)]);
+    let crew = get_crew(client().as_ref(), &server.url("/astros.json")).unwrap();
+
+    assert_eq!(crew.len(), 7);
+    assert_eq!(crew[0], "Oleg Kononenko");
+    assert_eq!(crew[6], "Alexander Grebenkin");
+}
+
+#[test]
+fn crew_without_people_is_a_missing_field() {
+    let server = StandIn::start(vec![Reply::Body(OPEN_NOTIFY_ASTROS_NO_PEOPLE)]);
+    let err = get_crew(client().as_ref(), &server.url("/astros.json")).unwrap_err();
+
+    assert!(matches!(err, IssError::MissingField(ref field) if field == "/people"), "{err}");
+}
+
+#[test]
+fn weather_in_requested_units() {
+    let server = StandIn::start(vec![Reply::Body(OPEN_METEO)]);
+    let units = WeatherUnits {
+        temperature: TemperatureUnit::Fahrenheit,
+        wind_speed: WindSpeedUnit::Mph,
+        ..WeatherUnits::default()
+    };
+    let weather = get_weather(client().as_ref(), &server.url("/v1/forecast"), 52.2, 0.12, &units).unwrap();
+
+    let path = server.requested();
+    assert!(path.starts_with("/v1/forecast?latitude=52.2&longitude=0.12&current="), "{path}");
+    assert!(path.contains("temperature_unit=fahrenheit"), "{path}");
+    assert!(path.contains("wind_speed_unit=mph"), "{path}");
+    assert_eq!(weather.temperature, 57.4);
+    assert_eq!(weather.temperature_unit, "°F");
+    assert_eq!(weather.code(), WmoCode::from_code(61));
+    assert_eq!(weather.wind_direction, 245.0);
+    assert!(weather.is_day);
+    assert_eq!(weather.precipitation_unit, "inch");
+}
+
+#[test]
+fn weather_without_current_conditions_is_invalid_json() {
+    let server = StandIn::start(vec![Reply::Body(OPEN_METEO_MISSING_CURRENT)]);
+    let err = get_weather(client().as_ref(), &server.url("/v1/forecast"), 0.0, 0.0, &WeatherUnits::default())
+        .unwrap_err();
+
+    assert!(matches!(err, IssError::Json(_)), "{err}");
+}
+
+#[test]
+fn truncated_payload_is_invalid_json() {
+    let server = StandIn::start(vec![Reply::Body(WHERE_THE_ISS_TRUNCATED)]);
+    let err = get_position(client().as_ref(), &server.url("/")).unwrap_err();
+
+    assert!(matches!(err, IssError::Json(_)), "{err}");
+}
+
+#[test]
+fn missing_altitude_is_a_missing_field() {
+    let server = StandIn::start(vec![Reply::Body(WHERE_THE_ISS_MISSING_ALTITUDE)]);
+    let err = get_position(client().as_ref(), &server.url("/")).unwrap_err();
+
+    assert!(matches!(err, IssError::MissingField(ref field) if field == "/altitude"), "{err}");
+}
+
+#[test]
+fn error_statuses_are_reported_with_the_url() {
+    let server = StandIn::start(vec![Reply::Status(429), Reply::Status(500)]);
+    let client = client();
+
+    for expected in [429, 500] {
+        let url = server.url("/astros.json");
+        match get_crew(client.as_ref(), &url).unwrap_err() {
+            IssError::HttpStatus { url: reported, status } => {
+                assert_eq!(status, expected);
+                assert_eq!(reported, url);
+            }
+            err => panic!("expected HTTP {expected}, got {err}"),
+        }
+    }
+}
+
+#[test]
+fn unanswered_requests_time_out() {
+    let server = StandIn::start(vec![Reply::Hang]);
+    let err = get_position(client().as_ref(), &server.url("/")).unwrap_err();
+
+    assert!(matches!(err, IssError::Network(ref err) if err.is_timeout()), "{err}");
+}
+
+#[test]
+fn failed_provider_falls_back_to_the_propagator() {
+    let server = StandIn::start(vec![Reply::Status(500)]);
+    let mut provider = WhereTheIss {
+        url: server.url("/"),
+        client: client(),
+    };
+    let sgp4 = Sgp4::new(Tle::parse(ISS_TLE).unwrap()).unwrap();
+
+    let (position, source) = fetch_with_fallback(&mut provider, Some(&sgp4)).unwrap();
+    assert_eq!(source, PositionSource::Propagated);
+    assert!((-52.0..=52.0).contains(&position.lat), "{}", position.lat);
+}
+
+#[test]
+fn tle_download() {
+    let server = StandIn::start(vec![Reply::Body(ISS_TLE)]);
+    let tle = fetch_tle(client().as_ref(), &server.url("/gp.php?CATNR=25544&FORMAT=TLE")).unwrap();
+
+    assert_eq!(server.requested(), "/gp.php?CATNR=25544&FORMAT=TLE");
+    assert_eq!(tle.norad_id, 25544);
+    assert_eq!(tle.name.as_deref(), Some("ISS (ZARYA)"));
+}
@@ -0,0 +1,95 @@
+//! Border crossings recorded as the ISS moves from one country, sea or ocean to another.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use iss_ratatui::iss::{Crossing, Iss, MAX_CROSSINGS};
+use iss_ratatui::provider::{Position, PositionSource};
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, 17, 13, 0, 0).unwrap()
+}
+
+/// Moves `iss` to `lat`, `lon`, `minutes` after [`now`].
+fn move_to(iss: &mut Iss, minutes: i64, lat: f64, lon: f64) {
+    iss.set_position(
+        Position {
+            lat,
+            lon,
+            alt: 415.0,
+            timestamp: (now() + Duration::minutes(minutes)).timestamp() as f64,
+        },
+        PositionSource::Live,
+    );
+}
+
+#[test]
+fn entering_a_new_region_is_a_crossing() {
+    let mut iss = Iss::new();
+
+    // The first position has nothing to cross from.
+    move_to(&mut iss, 0, 30.0, -40.0);
+    assert!(iss.crossings.is_empty());
+    assert_eq!(iss.crossing(), None);
+
+    move_to(&mut iss, 1, -10.0, -50.0);
+    let entered = Crossing {
+        time: (now() + Duration::minutes(1)).timestamp() as f64,
+        from: String::from("Atlantic Ocean"),
+        to: String::from("Brazil"),
+    };
+    assert_eq!(iss.crossings.len(), 1);
+    assert_eq!(iss.crossing(), Some(&entered));
+    assert_eq!(entered.to_string(), "Entered Brazil 13:01:00 UTC");
+
+    // Staying in Brazil adds nothing, and the crossing is no longer the latest position's.
+    move_to(&mut iss, 2, -11.0, -51.0);
+    assert_eq!(iss.crossings.len(), 1);
+    assert_eq!(iss.crossing(), None);
+}
+
+#[test]
+fn only_the_latest_crossings_are_kept() {
+    let mut iss = Iss::new();
+
+    // Back and forth between the Atlantic and Brazil, ending in Brazil.
+    for minute in 0..=MAX_CROSSINGS as i64 + 5 {
+        let (lat, lon) = if minute % 2 == 0 { (30.0, -40.0) } else { (-10.0, -50.0) };
+        move_to(&mut iss, minute, lat, lon);
+    }
+    assert_eq!(iss.crossings.len(), MAX_CROSSINGS);
+    assert_eq!(iss.crossing().map(|crossing| crossing.to.as_str()), Some("Brazil"));
+}
+
+#[test]
+fn a_failed_lookup_is_not_a_crossing() {
+    let mut iss = Iss::new();
+    move_to(&mut iss, 0, -10.0, -50.0);
+
+    // A position no outline contains, as a corrupt sample would be.
+    move_to(&mut iss, 1, f64::NAN, -50.0);
+    assert_eq!(iss.country, "Unknown Country");
+    assert_eq!(iss.crossing(), None);
+
+    move_to(&mut iss, 2, -11.0, -51.0);
+    assert_eq!(iss.country, "Brazil");
+    assert!(iss.crossings.is_empty());
+
+    // Leaving Brazil is measured from Brazil, not from the failed lookup.
+    move_to(&mut iss, 3, 30.0, -40.0);
+    move_to(&mut iss, 4, f64::NAN, -40.0);
+    move_to(&mut iss, 5, -10.0, -50.0);
+    let froms: Vec<&str> = iss.crossings.iter().map(|crossing| crossing.from.as_str()).collect();
+    assert_eq!(froms, ["Brazil", "Atlantic Ocean"]);
+}
+
+#[test]
+fn a_repeated_sample_does_not_repeat_the_crossing() {
+    let mut iss = Iss::new();
+    move_to(&mut iss, 0, 30.0, -40.0);
+    move_to(&mut iss, 1, -10.0, -50.0);
+    assert!(iss.crossing().is_some());
+
+    // The same answer again, timestamp and all.
+    move_to(&mut iss, 1, -10.0, -50.0);
+    assert_eq!(iss.crossing(), None);
+    assert_eq!(iss.crossings.len(), 1);
+}
@@ -0,0 +1,207 @@
+//! OEM ephemerides: parsing segments, interpolating between state vectors, and the
+//! rotation from EME2000 to the ground below.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use iss_ratatui::error::IssError;
+use iss_ratatui::oem::{OemEphemeris, DEFAULT_INTERPOLATION_DEGREE};
+use iss_ratatui::orbit::{Propagator, StateVector, MU_EARTH};
+
+const RADIUS_KM: f64 = 6778.0;
+const INCLINATION_DEG: f64 = 51.6;
+
+fn start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap()
+}
+
+/// The exact state on a circular two-body orbit, `seconds` after [`start`].
+fn circular(seconds: f64) -> StateVector {
+    let rate = (MU_EARTH / RADIUS_KM.powi(3)).sqrt();
+    let speed = rate * RADIUS_KM;
+    let (angle, inclination) = (rate * seconds, INCLINATION_DEG.to_radians());
+    let (sin, cos) = angle.sin_cos();
+
+    StateVector {
+        epoch: start() + Duration::milliseconds((seconds * 1000.0) as i64),
+        position: [
+            RADIUS_KM * cos,
+            RADIUS_KM * sin * inclination.cos(),
+            RADIUS_KM * sin * inclination.sin(),
+        ],
+        velocity: [
+            -speed * sin,
+            speed * cos * inclination.cos(),
+            speed * cos * inclination.sin(),
+        ],
+    }
+}
+
+fn state_line(state: &StateVector) -> String {
+    let [x, y, z] = state.position;
+    let [vx, vy, vz] = state.velocity;
+    format!(
+        "{} {x} {y} {z} {vx} {vy} {vz}",
+        state.epoch.format("%Y-%m-%dT%H:%M:%S%.3f")
+    )
+}
+
+/// One segment of the circular orbit, sampled every minute for `minutes`.
+fn segment(from_minute: i64, minutes: i64) -> String {
+    let mut text = String::from("META_START\nOBJECT_NAME = ISS\nREF_FRAME = EME2000\nMETA_STOP\n");
+    for minute in from_minute..=from_minute + minutes {
+        text += &state_line(&circular(minute as f64 * 60.0));
+        text.push('\n');
+    }
+    text
+}
+
+fn assert_close(actual: [f64; 3], expected: [f64; 3], tolerance: f64, what: &str) {
+    for axis in 0..3 {
+        assert!(
+            (actual[axis] - expected[axis]).abs() < tolerance,
+            "{what}: {actual:?} != {expected:?}"
+        );
+    }
+}
+
+#[test]
+fn a_node_epoch_returns_the_node_state() {
+    let ephemeris = OemEphemeris::parse(&segment(0, 30));
+    let node = ephemeris.segments[0].states[12];
+
+    assert_eq!(ephemeris.state_at(node.epoch).unwrap(), node);
+}
+
+#[test]
+fn between_nodes_follows_the_orbit() {
+    let ephemeris = OemEphemeris::parse(&segment(0, 30));
+
+    // Midway between nodes, at the start, middle and end of the segment.
+    for seconds in [30.0, 870.0, 1770.0] {
+        let state = ephemeris.state_at(circular(seconds).epoch).unwrap();
+        let expected = circular(seconds);
+        assert_close(
+            state.position,
+            expected.position,
+            1e-6,
+            &format!("position at {seconds} s"),
+        );
+        assert_close(
+            state.velocity,
+            expected.velocity,
+            1e-9,
+            &format!("velocity at {seconds} s"),
+        );
+    }
+}
+
+#[test]
+fn parses_segments_and_skips_everything_else() {
+    let mut first = segment(0, 10);
+    first = first.replace(
+        "META_STOP",
+        "INTERPOLATION = LAGRANGE\nINTERPOLATION_DEGREE = 5\nMETA_STOP",
+    );
+    let covariance = "\
+COVARIANCE_START
+EPOCH = 2024-06-01T00:10:00.000
+COV_REF_FRAME = RTN
+1.0e-3
+1.0e-6 1.0e-3
+COVARIANCE_STOP
+";
+    let header = "CCSDS_OEM_VERS = 2.0\nCOMMENT Generated for the tests\nCREATION_DATE = 2024-05-31T12:00:00\n";
+
+    // The later segment first, with its states out of order and one repeated.
+    let mut second = segment(20, 10);
+    second += &state_line(&circular(22.0 * 60.0));
+    second += "\n";
+    second += &state_line(&circular(21.0 * 60.0));
+    let ephemeris = OemEphemeris::parse(&format!("{header}{second}\n{first}{covariance}"));
+
+    assert_eq!(ephemeris.segments.len(), 2);
+    let [early, late] = [&ephemeris.segments[0], &ephemeris.segments[1]];
+    assert_eq!(early.interpolation_degree, 5);
+    assert_eq!(late.interpolation_degree, DEFAULT_INTERPOLATION_DEGREE);
+    assert_eq!(early.states.len(), 11);
+    assert_eq!(late.states.len(), 11);
+    assert!(late
+        .states
+        .windows(2)
+        .all(|pair| pair[0].epoch < pair[1].epoch));
+    assert_eq!(
+        ephemeris.span(),
+        Some((start(), start() + Duration::minutes(30)))
+    );
+}
+
+#[test]
+fn times_outside_the_ephemeris_are_errors() {
+    let ephemeris = OemEphemeris::parse(&format!("{}{}", segment(0, 10), segment(20, 10)));
+
+    for time in [
+        start() - Duration::seconds(1),
+        start() + Duration::minutes(15),
+        start() + Duration::minutes(30) + Duration::seconds(1),
+    ] {
+        match ephemeris.state_at(time) {
+            Err(IssError::Propagation(msg)) => {
+                assert!(msg.contains("is outside the OEM ephemeris"), "{msg}")
+            }
+            other => panic!("{time}: {other:?}"),
+        }
+    }
+
+    // Both ends are covered, as is a gap's edge.
+    for minutes in [0, 10, 20, 30] {
+        assert!(ephemeris
+            .state_at(start() + Duration::minutes(minutes))
+            .is_ok());
+    }
+
+    match OemEphemeris::parse("CCSDS_OEM_VERS = 2.0\n").state_at(start()) {
+        Err(IssError::Propagation(msg)) => assert_eq!(msg, "the OEM ephemeris is empty"),
+        other => panic!("{other:?}"),
+    }
+}
+
+#[test]
+fn eme2000_positions_are_rotated_under_the_earth() {
+    // At J2000 there is no precession, only the sidereal angle of 280.46°.
+    let j2000 = Utc.with_ymd_and_hms(2000, 1, 1, 12, 0, 0).unwrap();
+    let on_the_equator = StateVector {
+        epoch: j2000,
+        position: [RADIUS_KM, 0.0, 0.0],
+        velocity: [0.0, 7.67, 0.0],
+    };
+    let ephemeris = OemEphemeris::parse(&format!(
+        "META_START\nMETA_STOP\n{}\n",
+        state_line(&on_the_equator)
+    ));
+    let point = ephemeris.geodetic_at(j2000).unwrap();
+    assert!(point.lat.abs() < 1e-9, "{point:?}");
+    assert!(
+        (point.lon - (360.0 - 280.460_618)).abs() < 1e-5,
+        "{point:?}"
+    );
+    assert!(
+        (point.alt - (RADIUS_KM - 6378.137)).abs() < 1e-6,
+        "{point:?}"
+    );
+
+    // By mid-2024 precession has tipped the J2000 pole by about 491 arcseconds.
+    let time = Utc.with_ymd_and_hms(2024, 7, 2, 0, 0, 0).unwrap();
+    let over_the_pole = StateVector {
+        epoch: time,
+        position: [0.0, 0.0, RADIUS_KM],
+        velocity: [7.67, 0.0, 0.0],
+    };
+    let ephemeris = OemEphemeris::parse(&format!(
+        "META_START\nMETA_STOP\n{}\n",
+        state_line(&over_the_pole)
+    ));
+    let point = ephemeris.geodetic_at(time).unwrap();
+    assert!(
+        (point.lat - (90.0 - 491.0 / 3600.0)).abs() < 0.005,
+        "{point:?}"
+    );
+}
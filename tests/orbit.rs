@@ -0,0 +1,93 @@
+//! Osculating elements recovered from inertial states, and the perigee and apogee they predict.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use iss_ratatui::orbit::{OrbitalElements, StateVector, EQUATORIAL_RADIUS_KM, MU_EARTH};
+use nalgebra::{Rotation3, Vector3};
+
+fn epoch() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, 17, 13, 0, 0).unwrap()
+}
+
+/// The state on the orbit with the given elements (km and degrees), built in the perifocal
+/// frame and rotated out of it.
+fn state(semi_major_axis: f64, eccentricity: f64, inclination: f64, raan: f64, arg_perigee: f64, true_anomaly: f64) -> StateVector {
+    let (e, nu) = (eccentricity, true_anomaly.to_radians());
+    let semi_latus_rectum = semi_major_axis * (1.0 - e * e);
+    let radius = semi_latus_rectum / (1.0 + e * nu.cos());
+    let speed = (MU_EARTH / semi_latus_rectum).sqrt();
+
+    let to_inertial = Rotation3::from_axis_angle(&Vector3::z_axis(), raan.to_radians())
+        * Rotation3::from_axis_angle(&Vector3::x_axis(), inclination.to_radians())
+        * Rotation3::from_axis_angle(&Vector3::z_axis(), arg_perigee.to_radians());
+    let position = to_inertial * Vector3::new(radius * nu.cos(), radius * nu.sin(), 0.0);
+    let velocity = to_inertial * Vector3::new(-speed * nu.sin(), speed * (e + nu.cos()), 0.0);
+
+    StateVector {
+        epoch: epoch(),
+        position: position.into(),
+        velocity: velocity.into(),
+    }
+}
+
+fn assert_close(actual: f64, expected: f64, tolerance: f64, what: &str) {
+    assert!((actual - expected).abs() < tolerance, "{what}: {actual} != {expected}");
+}
+
+#[test]
+fn circular_equatorial_orbit() {
+    let radius = EQUATORIAL_RADIUS_KM + 400.0;
+    let elements = OrbitalElements::from_state(&StateVector {
+        epoch: epoch(),
+        position: [radius, 0.0, 0.0],
+        velocity: [0.0, (MU_EARTH / radius).sqrt(), 0.0],
+    });
+
+    assert_close(elements.semi_major_axis, radius, 1e-6, "semi-major axis");
+    assert_close(elements.eccentricity, 0.0, 1e-12, "eccentricity");
+    assert_close(elements.inclination, 0.0, 1e-12, "inclination");
+    assert_close(elements.perigee_altitude(), 400.0, 1e-6, "perigee altitude");
+    assert_close(elements.apogee_altitude(), 400.0, 1e-6, "apogee altitude");
+}
+
+#[test]
+fn eccentric_inclined_orbit() {
+    let elements = OrbitalElements::from_state(&state(7000.0, 0.05, 30.0, 40.0, 60.0, 90.0));
+
+    assert_eq!(elements.epoch, epoch());
+    assert_close(elements.semi_major_axis, 7000.0, 1e-6, "semi-major axis");
+    assert_close(elements.eccentricity, 0.05, 1e-12, "eccentricity");
+    assert_close(elements.inclination, 30.0, 1e-9, "inclination");
+    assert_close(elements.raan, 40.0, 1e-9, "RAAN");
+    assert_close(elements.arg_perigee, 60.0, 1e-9, "argument of perigee");
+    assert_close(elements.true_anomaly, 90.0, 1e-9, "true anomaly");
+    assert!(elements.is_ascending());
+}
+
+#[test]
+fn angles_past_half_a_turn_are_recovered() {
+    // Perigee south of the equator and the satellite falling back towards it.
+    let elements = OrbitalElements::from_state(&state(7200.0, 0.05, 98.0, 300.0, 250.0, 200.0));
+
+    assert_close(elements.inclination, 98.0, 1e-9, "inclination");
+    assert_close(elements.raan, 300.0, 1e-9, "RAAN");
+    assert_close(elements.arg_perigee, 250.0, 1e-9, "argument of perigee");
+    assert_close(elements.true_anomaly, 200.0, 1e-9, "true anomaly");
+    assert!(!elements.is_ascending());
+}
+
+#[test]
+fn perigee_and_apogee_are_predicted() {
+    let elements = OrbitalElements::from_state(&state(7000.0, 0.05, 30.0, 40.0, 60.0, 90.0));
+
+    // From a true anomaly of 90°, the mean anomaly is 1.47084 rad of a 5828.5 s period.
+    assert_close(elements.perigee_altitude(), 7000.0 * 0.95 - EQUATORIAL_RADIUS_KM, 1e-6, "perigee altitude");
+    assert_close(elements.apogee_altitude(), 7000.0 * 1.05 - EQUATORIAL_RADIUS_KM, 1e-6, "apogee altitude");
+    let seconds = |time: DateTime<Utc>| (time - epoch()).num_milliseconds() as f64 / 1000.0;
+    assert_close(seconds(elements.next_perigee()), 4464.113, 0.002, "time to perigee");
+    assert_close(seconds(elements.next_apogee()), 1549.854, 0.002, "time to apogee");
+
+    // Just past apogee, the next one is a whole period away.
+    let past_apogee = OrbitalElements::from_state(&state(7000.0, 0.05, 30.0, 40.0, 60.0, 180.001));
+    assert!(past_apogee.next_apogee() - epoch() > Duration::seconds(5828));
+    assert!(past_apogee.next_perigee() - epoch() < Duration::seconds(2915));
+}
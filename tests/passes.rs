@@ -0,0 +1,250 @@
+//! Pass prediction for a fixed observer from the fixture TLE.
+//!
+//! The reference table below is a one-second scan of the same SGP4 propagation, so it pins
+//! the search (coarse steps, bisection and the ternary search for the culmination) rather
+//! than the orbit, which `tests/tle.rs` checks against the published verification cases.
+//! The look angles and the Sun are checked on their own against worked values.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use iss_ratatui::orbit::Propagator;
+use iss_ratatui::passes::{compass_point, predict_passes, Observer, Pass, PASS_MIN_ELEVATION};
+use iss_ratatui::solar::{is_sunlit, sun_elevation};
+use iss_ratatui::tle::{Sgp4, Tle};
+
+const ISS_TLE: &str = include_str!("fixtures/iss.tle");
+
+/// Rise and its azimuth, culmination, its azimuth and the elevation there, set and its
+/// azimuth, and whether the pass can be seen.
+type Row = (&'static str, f64, &'static str, f64, f64, &'static str, f64, bool);
+
+/// Cambridge's passes in the 48 hours from the TLE's epoch that culminate at least 10° up, from a
+/// one-second scan for where the ISS is above the horizon.
+const REFERENCE: [Row; 9] = [
+    ("17 17:58:58", 220.15, "17 18:04:05", 148.86, 25.95, "17 18:09:12", 78.20, true),
+    ("17 19:35:05", 253.89, "17 19:40:30", 168.63, 70.25, "17 19:45:54", 82.88, true),
+    ("17 21:11:47", 274.98, "17 21:17:12", 188.69, 76.44, "17 21:22:36", 101.73, false),
+    ("17 22:48:29", 282.04, "17 22:53:42", 207.82, 31.47, "17 22:58:53", 133.52, false),
+    ("18 17:10:51", 209.37, "18 17:15:45", 144.19, 18.72, "18 17:20:39", 79.48, false),
+    ("18 18:46:39", 246.28, "18 18:52:02", 163.15, 57.97, "18 18:57:25", 80.27, true),
+    ("18 20:23:17", 270.81, "18 20:28:42", 185.17, 82.39, "18 20:34:07", 95.52, true),
+    ("18 21:59:59", 281.59, "18 22:05:18", 202.81, 42.31, "18 22:10:35", 124.12, false),
+    ("18 23:36:56", 277.10, "18 23:41:23", 221.47, 11.83, "18 23:45:49", 165.46, false),
+];
+
+fn observer() -> Observer {
+    Observer {
+        name: String::from("Cambridge"),
+        lat: 52.2053,
+        lon: 0.1218,
+        elevation: 10.0,
+    }
+}
+
+/// The fixture TLE's epoch.
+fn epoch() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap()
+}
+
+/// `day hh:mm:ss` in October 2026.
+fn at(text: &str) -> DateTime<Utc> {
+    let (day, time) = text.split_once(' ').unwrap();
+    let fields: Vec<u32> = time
+        .split(':')
+        .map(|field| field.parse().unwrap())
+        .collect();
+    Utc.with_ymd_and_hms(
+        2026,
+        10,
+        day.parse().unwrap(),
+        fields[0],
+        fields[1],
+        fields[2],
+    )
+    .unwrap()
+}
+
+fn passes() -> (Sgp4, Vec<Pass>) {
+    let sgp4 = Sgp4::new(Tle::parse(ISS_TLE).unwrap()).unwrap();
+    let passes = predict_passes(&sgp4, &observer(), epoch(), 48, PASS_MIN_ELEVATION);
+    (sgp4, passes)
+}
+
+fn assert_near(actual: DateTime<Utc>, expected: DateTime<Utc>, what: &str) {
+    assert!(
+        (actual - expected).num_seconds().abs() <= 1,
+        "{what}: {actual} != {expected}"
+    );
+}
+
+#[test]
+fn passes_match_the_reference_table() {
+    let (_, passes) = passes();
+    assert_eq!(passes.len(), REFERENCE.len());
+
+    for (pass, &(rise, rise_az, top, top_az, max_elevation, set, set_az, visible)) in
+        passes.iter().zip(&REFERENCE)
+    {
+        assert_near(pass.rise, at(rise), "rise");
+        assert_near(pass.culmination, at(top), "culmination");
+        assert_near(pass.set, at(set), "set");
+        // A second either way moves the azimuth by up to 0.2° at the horizon, and by more than
+        // a degree near the zenith.
+        for (actual, expected, tolerance) in [
+            (pass.rise_azimuth, rise_az, 0.5),
+            (pass.culmination_azimuth, top_az, 2.0),
+            (pass.set_azimuth, set_az, 0.5),
+        ] {
+            assert!(
+                (actual - expected).abs() < tolerance,
+                "azimuth at {rise}: {actual} != {expected}"
+            );
+        }
+        assert!(
+            (pass.max_elevation - max_elevation).abs() < 0.01,
+            "{pass:?}"
+        );
+        assert_eq!(pass.visible, visible, "{pass:?}");
+    }
+}
+
+#[test]
+fn passes_rise_culminate_and_set_in_order() {
+    let (sgp4, passes) = passes();
+    for pass in &passes {
+        assert!(
+            pass.rise < pass.culmination && pass.culmination < pass.set,
+            "{pass:?}"
+        );
+        assert!(pass.max_elevation >= PASS_MIN_ELEVATION, "{pass:?}");
+        assert!(observer().look_angles(&sgp4.ecef_at(pass.rise).unwrap()).1.abs() < 0.1, "{pass:?}");
+        assert!(observer().look_angles(&sgp4.ecef_at(pass.set).unwrap()).1.abs() < 0.1, "{pass:?}");
+    }
+    assert!(passes.windows(2).all(|pair| pair[0].set < pair[1].rise));
+
+    // Evening passes over England climb out of the west and set in the east.
+    let first = &passes[0];
+    assert_eq!(compass_point(first.rise_azimuth), "SW");
+    assert_eq!(compass_point(first.culmination_azimuth), "SE");
+    assert_eq!(compass_point(first.set_azimuth), "E");
+}
+
+#[test]
+fn passes_agree_with_a_one_second_scan() {
+    let (sgp4, passes) = passes();
+    let look = |time: DateTime<Utc>| observer().look_angles(&sgp4.ecef_at(time).unwrap());
+
+    for pass in &passes {
+        let seconds = (pass.set - pass.rise).num_seconds() + 120;
+        let scan: Vec<(DateTime<Utc>, f64)> = (0..=seconds)
+            .map(|second| pass.rise - Duration::seconds(60) + Duration::seconds(second))
+            .map(|time| (time, look(time).1))
+            .collect();
+        let above: Vec<&(DateTime<Utc>, f64)> = scan
+            .iter()
+            .filter(|(_, elevation)| *elevation >= 0.0)
+            .collect();
+        let highest = scan.iter().max_by(|a, b| a.1.total_cmp(&b.1)).unwrap();
+
+        assert_near(pass.rise, above[0].0, "rise");
+        assert_near(pass.set, above[above.len() - 1].0, "set");
+        assert!((pass.max_elevation - highest.1).abs() < 0.01, "{pass:?}");
+        assert!((look(pass.culmination).1 - pass.max_elevation).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn a_pass_is_visible_only_when_the_iss_is_sunlit_and_the_observer_is_dark() {
+    let (sgp4, passes) = passes();
+    let observer = observer();
+    let samples = |pass: &Pass| {
+        let (rise, seconds) = (pass.rise, (pass.set - pass.rise).num_seconds());
+        (0..=seconds).step_by(10).map(move |second| rise + Duration::seconds(second))
+    };
+
+    // Shortly after sunset: the ISS catches the Sun, but the sky is still too bright.
+    let twilight = &passes[4];
+    assert!(!twilight.visible);
+    assert!(samples(twilight).any(|time| is_sunlit(&sgp4.ecef_at(time).unwrap(), time)));
+    assert!(samples(twilight).all(|time| sun_elevation(observer.lat, observer.lon, time) > -6.0));
+
+    // Late in the evening: the sky is dark, but so is the ISS, inside the Earth's shadow.
+    let eclipsed = &passes[2];
+    assert!(!eclipsed.visible);
+    assert!(samples(eclipsed).all(|time| sun_elevation(observer.lat, observer.lon, time) < -6.0));
+    assert!(samples(eclipsed).all(|time| !is_sunlit(&sgp4.ecef_at(time).unwrap(), time)));
+
+    // In between, both.
+    let visible = &passes[1];
+    assert!(visible.visible);
+    assert!(samples(visible).any(|time| {
+        is_sunlit(&sgp4.ecef_at(time).unwrap(), time)
+            && sun_elevation(observer.lat, observer.lon, time) < -6.0
+    }));
+}
+
+#[test]
+fn a_pass_in_progress_is_reported_from_the_start() {
+    let (sgp4, passes) = passes();
+    let pass = &passes[1];
+    let started = pass.culmination;
+
+    let from_culmination = predict_passes(&sgp4, &observer(), started, 1, PASS_MIN_ELEVATION);
+    assert_eq!(from_culmination[0].rise, started);
+    assert_near(from_culmination[0].set, pass.set, "set");
+}
+
+#[test]
+fn low_passes_are_left_out_but_still_rise_at_the_horizon() {
+    let (sgp4, passes) = passes();
+    let all = predict_passes(&sgp4, &observer(), epoch(), 48, 0.0);
+    assert_eq!(all.len(), 12);
+    let low: Vec<&Pass> = all.iter().filter(|pass| !passes.contains(pass)).collect();
+    assert_eq!(low.len(), 3);
+    assert!(low.iter().all(|pass| pass.max_elevation < PASS_MIN_ELEVATION), "{low:?}");
+    assert_near(low[0].rise, at("17 16:24:52"), "rise");
+    assert_near(low[0].set, at("17 16:31:40"), "set");
+}
+
+#[test]
+fn look_angles_match_worked_values() {
+    // From the equator at 0°, straight up along the x axis and then north or east of it.
+    let equator = Observer {
+        name: String::from("Null Island"),
+        lat: 0.0,
+        lon: 0.0,
+        elevation: 0.0,
+    };
+    let (azimuth, elevation) = equator.look_angles(&[6478.137, 0.0, 100.0]);
+    assert!(azimuth.abs() < 1e-9 && (elevation - 45.0).abs() < 1e-9, "{azimuth}, {elevation}");
+    let (azimuth, elevation) = equator.look_angles(&[6378.137, 100.0, 0.0]);
+    assert!((azimuth - 90.0).abs() < 1e-9 && elevation.abs() < 1e-9, "{azimuth}, {elevation}");
+
+    // A dish in Cambridge pointed at a geostationary satellite over 0°: the usual pointing
+    // formulas give an azimuth of 180 + atan(tan 0.1218° / sin 52.2053°) = 180.15° and an
+    // elevation of about 30.3°.
+    let (azimuth, elevation) = observer().look_angles(&[42164.0, 0.0, 0.0]);
+    assert!((azimuth - 180.15).abs() < 0.01, "{azimuth}");
+    assert!((elevation - 30.3).abs() < 0.1, "{elevation}");
+}
+
+#[test]
+fn a_daylight_pass_is_not_visible_and_one_after_dusk_is() {
+    // In Cambridge on 18 October 2026 the Sun sets at about 16:57 UTC and civil twilight ends at
+    // about 17:33 UTC.
+    let observer = observer();
+    let sun = |text: &str| sun_elevation(observer.lat, observer.lon, at(text));
+    assert!(sun("18 16:45:00") > 0.0 && sun("18 17:00:00") < 0.0);
+    assert!(sun("18 17:30:00") > -6.0 && sun("18 17:40:00") < -6.0);
+
+    let sgp4 = Sgp4::new(Tle::parse(ISS_TLE).unwrap()).unwrap();
+    let all = predict_passes(&sgp4, &observer, epoch(), 48, 0.0);
+    let daylight = all.iter().find(|pass| pass.rise > at("18 15:00:00")).unwrap();
+    assert_near(daylight.rise, at("18 15:38:02"), "rise");
+    assert!(!daylight.visible);
+
+    // The evening after, the first pass rises about 25 minutes after dusk, with the ISS still
+    // high enough to catch the Sun.
+    let after_dusk = all.iter().find(|pass| pass.rise > at("17 17:33:00")).unwrap();
+    assert_near(after_dusk.rise, at("17 17:58:58"), "rise");
+    assert!(after_dusk.visible);
+}
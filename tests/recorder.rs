@@ -0,0 +1,107 @@
+//! Recordings written and read back in both formats, and the replay they drive.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use iss_ratatui::error::IssError;
+use iss_ratatui::provider::PositionSource;
+use iss_ratatui::recorder::{read_samples, Recorder, Replay, Sample};
+use std::path::{Path, PathBuf};
+
+/// A fresh path for `name` under the target directory.
+fn temp_path(name: &str) -> PathBuf {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("recorder").join(name);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, 17, 13, 0, 0).unwrap()
+}
+
+/// Samples with every source, and countries that need quoting in CSV.
+fn samples() -> Vec<Sample> {
+    let sample = |seconds: i64, lat: f64, lon: f64, country: &str, source: PositionSource| Sample {
+        time: start() + Duration::seconds(seconds),
+        lat,
+        lon,
+        alt: 415.123_456,
+        country: String::from(country),
+        source,
+    };
+    vec![
+        sample(0, 12.5, -40.25, "Atlantic Ocean", PositionSource::Live),
+        sample(5, -0.000_1, 179.999_9, "Korea, Republic of", PositionSource::Propagated),
+        sample(10, 51.477_928, -0.001_545, "The \"United\" Kingdom", PositionSource::Live),
+        sample(15, -89.5, 0.0, "Unknown Country", PositionSource::Propagated),
+    ]
+}
+
+fn record(path: &Path, samples: &[Sample]) {
+    let mut recorder = Recorder::open(path).unwrap();
+    for sample in samples {
+        recorder.record(sample).unwrap();
+    }
+}
+
+#[test]
+fn json_lines_round_trip() {
+    let path = temp_path("track.jsonl");
+    record(&path, &samples());
+    assert_eq!(read_samples(&path).unwrap(), samples());
+}
+
+#[test]
+fn csv_round_trips() {
+    let path = temp_path("track.csv");
+    record(&path, &samples());
+    assert_eq!(read_samples(&path).unwrap(), samples());
+
+    // Reopening appends, without a second header.
+    record(&path, &samples()[..1]);
+    let text = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(text.lines().filter(|line| line.starts_with("time,")).count(), 1);
+    assert_eq!(read_samples(&path).unwrap().len(), samples().len() + 1);
+}
+
+#[test]
+fn samples_are_read_back_in_time_order() {
+    let path = temp_path("unordered.jsonl");
+    let mut reversed = samples();
+    reversed.reverse();
+    record(&path, &reversed);
+    assert_eq!(read_samples(&path).unwrap(), samples());
+}
+
+#[test]
+fn a_malformed_line_is_reported_with_its_number() {
+    for (name, line, text) in [
+        ("broken.jsonl", 2, "{\"time\":\"2026-10-17T13:00:00Z\",\"lat\":1.0,\"lon\":2.0,\"alt\":415.0,\"country\":\"Chad\",\"source\":\"LIVE\"}\n{\"time\":\"2026-10-17T13:00:05Z\",\"lat\":\n"),
+        ("broken.csv", 3, "time,lat,lon,alt,country,source\n2026-10-17T13:00:00+00:00,1,2,415,Chad,LIVE\n2026-10-17T13:00:05+00:00,north,2,415,Chad,LIVE\n"),
+        ("short.csv", 3, "time,lat,lon,alt,country,source\n2026-10-17T13:00:00+00:00,1,2,415,Chad,LIVE\n2026-10-17T13:00:05+00:00,1,2\n"),
+    ] {
+        let path = temp_path(name);
+        std::fs::write(&path, text).unwrap();
+        match read_samples(&path) {
+            Err(IssError::InvalidRecord(msg)) => {
+                assert!(msg.starts_with(&format!("{} line {line}: ", path.display())), "{msg}")
+            }
+            other => panic!("{name}: {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn a_replay_needs_samples_and_a_forward_speed() {
+    assert!(matches!(Replay::new(Vec::new(), 1.0), Err(IssError::InvalidRecord(_))));
+
+    for speed in [0.0, -1.0, -0.5, f64::NAN, f64::INFINITY] {
+        match Replay::new(samples(), speed) {
+            Err(IssError::InvalidConfig(msg)) => assert!(msg.contains("greater than 0"), "{msg}"),
+            other => panic!("{speed}: {other:?}"),
+        }
+    }
+
+    let replay = Replay::new(samples(), 0.125).unwrap();
+    assert_eq!(replay.clock(), start());
+    assert!(!replay.is_finished());
+}
@@ -0,0 +1,320 @@
+//! The embedded country, sea and ocean outlines the browser build geocodes with.
+
+use iss_ratatui::regions::{locate, parse_regions, RegionKind};
+
+/// Every member of the United Nations, with its capital and where that is.
+const UN_CAPITALS: [(&str, &str, f64, f64); 193] = [
+    ("Afghanistan", "Kabul", 34.53, 69.17),
+    ("Albania", "Tirana", 41.33, 19.82),
+    ("Algeria", "Algiers", 36.75, 3.06),
+    ("Andorra", "Andorra la Vella", 42.51, 1.52),
+    ("Angola", "Luanda", -8.84, 13.23),
+    ("Antigua and Barbuda", "St. John's", 17.12, -61.85),
+    ("Argentina", "Buenos Aires", -34.6, -58.38),
+    ("Armenia", "Yerevan", 40.18, 44.51),
+    ("Australia", "Canberra", -35.28, 149.13),
+    ("Austria", "Vienna", 48.21, 16.37),
+    ("Azerbaijan", "Baku", 40.41, 49.87),
+    ("Bahamas", "Nassau", 25.05, -77.35),
+    ("Bahrain", "Manama", 26.23, 50.58),
+    ("Bangladesh", "Dhaka", 23.81, 90.41),
+    ("Barbados", "Bridgetown", 13.1, -59.62),
+    ("Belarus", "Minsk", 53.9, 27.57),
+    ("Belgium", "Brussels", 50.85, 4.35),
+    ("Belize", "Belmopan", 17.25, -88.77),
+    ("Benin", "Porto-Novo", 6.5, 2.6),
+    ("Bhutan", "Thimphu", 27.47, 89.64),
+    ("Bolivia", "Sucre", -19.04, -65.26),
+    ("Bosnia and Herzegovina", "Sarajevo", 43.86, 18.41),
+    ("Botswana", "Gaborone", -24.65, 25.91),
+    ("Brazil", "Brasilia", -15.79, -47.88),
+    ("Brunei", "Bandar Seri Begawan", 4.89, 114.94),
+    ("Bulgaria", "Sofia", 42.7, 23.32),
+    ("Burkina Faso", "Ouagadougou", 12.37, -1.52),
+    ("Burundi", "Gitega", -3.43, 29.92),
+    ("Cambodia", "Phnom Penh", 11.56, 104.92),
+    ("Cameroon", "Yaounde", 3.87, 11.52),
+    ("Canada", "Ottawa", 45.42, -75.7),
+    ("Cape Verde", "Praia", 14.93, -23.51),
+    ("Central African Republic", "Bangui", 4.39, 18.56),
+    ("Chad", "N'Djamena", 12.13, 15.06),
+    ("Chile", "Santiago", -33.45, -70.67),
+    ("China", "Beijing", 39.9, 116.4),
+    ("Colombia", "Bogota", 4.71, -74.07),
+    ("Comoros", "Moroni", -11.7, 43.26),
+    ("Democratic Republic of the Congo", "Kinshasa", -4.32, 15.31),
+    ("Republic of the Congo", "Brazzaville", -4.27, 15.28),
+    ("Costa Rica", "San Jose", 9.93, -84.08),
+    ("Ivory Coast", "Yamoussoukro", 6.83, -5.29),
+    ("Croatia", "Zagreb", 45.81, 15.98),
+    ("Cuba", "Havana", 23.11, -82.37),
+    ("Cyprus", "Nicosia", 35.17, 33.36),
+    ("Czechia", "Prague", 50.08, 14.44),
+    ("Denmark", "Copenhagen", 55.68, 12.57),
+    ("Djibouti", "Djibouti", 11.59, 43.15),
+    ("Dominica", "Roseau", 15.3, -61.39),
+    ("Dominican Republic", "Santo Domingo", 18.47, -69.9),
+    ("East Timor", "Dili", -8.56, 125.57),
+    ("Ecuador", "Quito", -0.18, -78.47),
+    ("Egypt", "Cairo", 30.04, 31.24),
+    ("El Salvador", "San Salvador", 13.69, -89.19),
+    ("Equatorial Guinea", "Malabo", 3.75, 8.78),
+    ("Eritrea", "Asmara", 15.32, 38.93),
+    ("Estonia", "Tallinn", 59.44, 24.75),
+    ("Eswatini", "Mbabane", -26.31, 31.14),
+    ("Ethiopia", "Addis Ababa", 9.03, 38.74),
+    ("Fiji", "Suva", -18.14, 178.44),
+    ("Finland", "Helsinki", 60.17, 24.94),
+    ("France", "Paris", 48.86, 2.35),
+    ("Gabon", "Libreville", 0.42, 9.47),
+    ("Gambia", "Banjul", 13.45, -16.58),
+    ("Georgia", "Tbilisi", 41.72, 44.79),
+    ("Germany", "Berlin", 52.52, 13.4),
+    ("Ghana", "Accra", 5.6, -0.19),
+    ("Greece", "Athens", 37.98, 23.73),
+    ("Grenada", "St. George's", 12.05, -61.75),
+    ("Guatemala", "Guatemala City", 14.63, -90.51),
+    ("Guinea", "Conakry", 9.51, -13.71),
+    ("Guinea-Bissau", "Bissau", 11.86, -15.6),
+    ("Guyana", "Georgetown", 6.8, -58.16),
+    ("Haiti", "Port-au-Prince", 18.54, -72.34),
+    ("Honduras", "Tegucigalpa", 14.07, -87.19),
+    ("Hungary", "Budapest", 47.5, 19.04),
+    ("Iceland", "Reykjavik", 64.15, -21.94),
+    ("India", "New Delhi", 28.61, 77.21),
+    ("Indonesia", "Jakarta", -6.21, 106.85),
+    ("Iran", "Tehran", 35.69, 51.39),
+    ("Iraq", "Baghdad", 33.31, 44.36),
+    ("Ireland", "Dublin", 53.35, -6.26),
+    ("Israel", "Jerusalem", 31.777, 35.205),
+    ("Italy", "Rome", 41.9, 12.5),
+    ("Jamaica", "Kingston", 17.97, -76.79),
+    ("Japan", "Tokyo", 35.68, 139.69),
+    ("Jordan", "Amman", 31.95, 35.93),
+    ("Kazakhstan", "Astana", 51.17, 71.45),
+    ("Kenya", "Nairobi", -1.29, 36.82),
+    ("Kiribati", "Tarawa", 1.33, 172.98),
+    ("North Korea", "Pyongyang", 39.03, 125.75),
+    ("South Korea", "Seoul", 37.57, 126.98),
+    ("Kuwait", "Kuwait City", 29.37, 47.98),
+    ("Kyrgyzstan", "Bishkek", 42.87, 74.59),
+    ("Laos", "Vientiane", 17.97, 102.6),
+    ("Latvia", "Riga", 56.95, 24.1),
+    ("Lebanon", "Beirut", 33.89, 35.5),
+    ("Lesotho", "Maseru", -29.31, 27.48),
+    ("Liberia", "Monrovia", 6.3, -10.8),
+    ("Libya", "Tripoli", 32.89, 13.19),
+    ("Liechtenstein", "Vaduz", 47.14, 9.52),
+    ("Lithuania", "Vilnius", 54.69, 25.28),
+    ("Luxembourg", "Luxembourg", 49.61, 6.13),
+    ("Madagascar", "Antananarivo", -18.88, 47.51),
+    ("Malawi", "Lilongwe", -13.96, 33.79),
+    ("Malaysia", "Kuala Lumpur", 3.139, 101.69),
+    ("Maldives", "Male", 4.18, 73.51),
+    ("Mali", "Bamako", 12.64, -8.0),
+    ("Malta", "Valletta", 35.9, 14.51),
+    ("Marshall Islands", "Majuro", 7.09, 171.38),
+    ("Mauritania", "Nouakchott", 18.08, -15.98),
+    ("Mauritius", "Port Louis", -20.16, 57.5),
+    ("Mexico", "Mexico City", 19.43, -99.13),
+    ("Micronesia", "Palikir", 6.92, 158.16),
+    ("Moldova", "Chisinau", 47.01, 28.86),
+    ("Monaco", "Monaco", 43.73, 7.42),
+    ("Mongolia", "Ulaanbaatar", 47.89, 106.91),
+    ("Montenegro", "Podgorica", 42.44, 19.26),
+    ("Morocco", "Rabat", 34.02, -6.84),
+    ("Mozambique", "Maputo", -25.97, 32.57),
+    ("Myanmar", "Naypyidaw", 19.76, 96.08),
+    ("Namibia", "Windhoek", -22.56, 17.08),
+    ("Nauru", "Yaren", -0.55, 166.92),
+    ("Nepal", "Kathmandu", 27.72, 85.32),
+    ("Netherlands", "Amsterdam", 52.37, 4.9),
+    ("New Zealand", "Wellington", -41.29, 174.78),
+    ("Nicaragua", "Managua", 12.11, -86.24),
+    ("Niger", "Niamey", 13.51, 2.11),
+    ("Nigeria", "Abuja", 9.08, 7.4),
+    ("North Macedonia", "Skopje", 42.0, 21.43),
+    ("Norway", "Oslo", 59.91, 10.75),
+    ("Oman", "Muscat", 23.59, 58.41),
+    ("Pakistan", "Islamabad", 33.68, 73.05),
+    ("Palau", "Ngerulmud", 7.5, 134.62),
+    ("Panama", "Panama City", 8.98, -79.52),
+    ("Papua New Guinea", "Port Moresby", -9.44, 147.18),
+    ("Paraguay", "Asuncion", -25.26, -57.58),
+    ("Peru", "Lima", -12.05, -77.04),
+    ("Philippines", "Manila", 14.6, 120.98),
+    ("Poland", "Warsaw", 52.23, 21.01),
+    ("Portugal", "Lisbon", 38.72, -9.14),
+    ("Qatar", "Doha", 25.29, 51.53),
+    ("Romania", "Bucharest", 44.43, 26.1),
+    ("Russia", "Moscow", 55.76, 37.62),
+    ("Rwanda", "Kigali", -1.94, 30.06),
+    ("Saint Kitts and Nevis", "Basseterre", 17.3, -62.73),
+    ("Saint Lucia", "Castries", 14.01, -60.99),
+    ("Saint Vincent and the Grenadines", "Kingstown", 13.16, -61.22),
+    ("Samoa", "Apia", -13.83, -171.76),
+    ("San Marino", "San Marino", 43.94, 12.45),
+    ("Sao Tome and Principe", "Sao Tome", 0.34, 6.73),
+    ("Saudi Arabia", "Riyadh", 24.71, 46.68),
+    ("Senegal", "Dakar", 14.72, -17.47),
+    ("Serbia", "Belgrade", 44.79, 20.45),
+    ("Seychelles", "Victoria", -4.62, 55.45),
+    ("Sierra Leone", "Freetown", 8.48, -13.23),
+    ("Singapore", "Singapore", 1.29, 103.85),
+    ("Slovakia", "Bratislava", 48.15, 17.11),
+    ("Slovenia", "Ljubljana", 46.06, 14.51),
+    ("Solomon Islands", "Honiara", -9.43, 159.95),
+    ("Somalia", "Mogadishu", 2.05, 45.32),
+    ("South Africa", "Pretoria", -25.75, 28.19),
+    ("South Sudan", "Juba", 4.85, 31.58),
+    ("Spain", "Madrid", 40.42, -3.7),
+    ("Sri Lanka", "Sri Jayawardenepura Kotte", 6.9, 79.9),
+    ("Sudan", "Khartoum", 15.5, 32.56),
+    ("Suriname", "Paramaribo", 5.85, -55.2),
+    ("Sweden", "Stockholm", 59.33, 18.07),
+    ("Switzerland", "Bern", 46.95, 7.45),
+    ("Syria", "Damascus", 33.51, 36.29),
+    ("Tajikistan", "Dushanbe", 38.56, 68.77),
+    ("Tanzania", "Dodoma", -6.16, 35.75),
+    ("Thailand", "Bangkok", 13.76, 100.5),
+    ("Togo", "Lome", 6.13, 1.22),
+    ("Tonga", "Nuku'alofa", -21.14, -175.2),
+    ("Trinidad and Tobago", "Port of Spain", 10.65, -61.5),
+    ("Tunisia", "Tunis", 36.8, 10.18),
+    ("Turkey", "Ankara", 39.93, 32.86),
+    ("Turkmenistan", "Ashgabat", 37.96, 58.33),
+    ("Tuvalu", "Funafuti", -8.52, 179.2),
+    ("Uganda", "Kampala", 0.35, 32.58),
+    ("Ukraine", "Kyiv", 50.45, 30.52),
+    ("United Arab Emirates", "Abu Dhabi", 24.45, 54.38),
+    ("United Kingdom", "London", 51.51, -0.13),
+    ("United States", "Washington", 38.9, -77.04),
+    ("Uruguay", "Montevideo", -34.9, -56.16),
+    ("Uzbekistan", "Tashkent", 41.3, 69.24),
+    ("Vanuatu", "Port Vila", -17.73, 168.32),
+    ("Venezuela", "Caracas", 10.49, -66.88),
+    ("Vietnam", "Hanoi", 21.03, 105.85),
+    ("Yemen", "Sanaa", 15.37, 44.19),
+    ("Zambia", "Lusaka", -15.39, 28.32),
+    ("Zimbabwe", "Harare", -17.83, 31.05),
+];
+
+fn name_at(lat: f64, lon: f64) -> Option<&'static str> {
+    locate(lat, lon).map(|region| region.name.as_str())
+}
+
+#[test]
+fn capitals_are_in_their_countries() {
+    for (lat, lon, country) in [
+        (38.9, -77.04, "United States"),
+        (-15.79, -47.88, "Brazil"),
+        (51.51, -0.13, "United Kingdom"),
+        (-1.29, 36.82, "Kenya"),
+        (28.6, 77.2, "India"),
+        (39.9, 116.4, "China"),
+        (-35.28, 149.13, "Australia"),
+    ] {
+        assert_eq!(name_at(lat, lon), Some(country), "{lat}, {lon}");
+    }
+}
+
+#[test]
+fn every_un_member_capital_is_in_its_country() {
+    for (country, capital, lat, lon) in UN_CAPITALS {
+        assert_eq!(name_at(lat, lon), Some(country), "{capital} ({lat}, {lon})");
+    }
+}
+
+#[test]
+fn open_water_is_an_ocean() {
+    for (lat, lon, ocean) in [
+        (30.0, -40.0, "Atlantic Ocean"),
+        (0.0, -150.0, "Pacific Ocean"),
+        (0.0, 170.0, "Pacific Ocean"),
+        (-20.0, 80.0, "Indian Ocean"),
+        (-65.0, 0.0, "Southern Ocean"),
+    ] {
+        let region = locate(lat, lon).expect("every ocean point is covered");
+        assert_eq!(
+            (region.name.as_str(), region.kind),
+            (ocean, RegionKind::Ocean),
+            "{lat}, {lon}"
+        );
+    }
+}
+
+#[test]
+fn seas_win_over_the_ocean_around_them() {
+    for (lat, lon, sea) in [
+        (35.0, 18.0, "Mediterranean Sea"),
+        (15.0, -75.0, "Caribbean Sea"),
+        (27.0, 51.0, "Persian Gulf"),
+        (-40.0, 170.0, "Tasman Sea"),
+    ] {
+        let region = locate(lat, lon).expect("every sea point is covered");
+        assert_eq!(
+            (region.name.as_str(), region.kind),
+            (sea, RegionKind::Sea),
+            "{lat}, {lon}"
+        );
+    }
+}
+
+#[test]
+fn seas_reach_the_coasts_around_them() {
+    // Water between a coarse coast and the middle of the sea: Kuwait Bay, the top of the Gulf
+    // of Bothnia and the Strait of Tartary.
+    for (lat, lon, sea) in [
+        (29.45, 47.9, "Persian Gulf"),
+        (65.2, 24.0, "Baltic Sea"),
+        (51.0, 141.3, "Sea of Japan"),
+    ] {
+        assert_eq!(name_at(lat, lon), Some(sea), "{lat}, {lon}");
+    }
+
+    // Capitals on the shore are in their country or its sea, never the ocean beyond.
+    for (lat, lon, country, sea) in [
+        (29.37, 47.98, "Kuwait", "Persian Gulf"),
+        (60.17, 24.94, "Finland", "Baltic Sea"),
+        (32.89, 13.19, "Libya", "Mediterranean Sea"),
+        (40.41, 49.87, "Azerbaijan", "Caspian Sea"),
+    ] {
+        let name = name_at(lat, lon).unwrap();
+        assert!(name == country || name == sea, "{lat}, {lon} is in {name}");
+    }
+}
+
+#[test]
+fn enclaves_win_over_the_country_around_them() {
+    assert_eq!(name_at(-29.31, 27.48), Some("Lesotho"));
+    assert_eq!(name_at(13.45, -16.58), Some("Gambia"));
+}
+
+#[test]
+fn antarctica_is_land_up_to_the_pole() {
+    for (lat, lon) in [(-80.0, 0.0), (-75.0, 100.0), (-89.0, 0.0), (-90.0, -120.0), (-85.0, 180.0), (-64.0, -58.0)] {
+        let region = locate(lat, lon).expect("Antarctica is covered");
+        assert_eq!((region.name.as_str(), region.kind), ("Antarctica", RegionKind::Country), "{lat}, {lon}");
+    }
+    // The Ross and Weddell Seas cut into the coast.
+    assert_eq!(name_at(-74.0, -175.0), Some("Southern Ocean"));
+    assert_eq!(name_at(-72.0, -40.0), Some("Southern Ocean"));
+}
+
+#[test]
+fn longitudes_wrap_around() {
+    assert_eq!(name_at(66.0, -172.0), Some("Russia"));
+    assert_eq!(name_at(66.0, 188.0), Some("Russia"));
+}
+
+#[test]
+fn parses_and_rejects_outlines() {
+    let regions = parse_regions("# comment\ncountry Square\n  0 0; 2 0;\n  2 2; 0 2\n").unwrap();
+    assert_eq!(regions.len(), 1);
+    assert!(regions[0].contains(1.0, 1.0));
+    assert!(!regions[0].contains(3.0, 1.0));
+
+    assert!(parse_regions("lake Square\n  0 0; 2 0; 2 2\n").is_err());
+    assert!(parse_regions("country Line\n  0 0; 2 0\n").is_err());
+    assert!(parse_regions("country Square\n  0 0; 200 0; 2 2\n").is_err());
+}
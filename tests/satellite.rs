@@ -0,0 +1,62 @@
+//! Satellites tracked alongside the ISS, propagated from the fixture TLEs.
+
+use chrono::{DateTime, TimeZone, Utc};
+use iss_ratatui::config::Providers;
+use iss_ratatui::iss::MAX_POS_DATA;
+use iss_ratatui::orbit::Propagator;
+use iss_ratatui::satellite::{Satellite, ISS_NORAD_ID};
+use iss_ratatui::tle::{Sgp4, Tle};
+
+const TIANGONG_TLE: &str = include_str!("fixtures/tiangong.tle");
+
+/// Shortly after the fixture TLE's epoch.
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, 17, 13, 0, 0).unwrap()
+}
+
+#[test]
+fn named_after_the_tle_unless_given_a_name() {
+    let tle = Tle::parse(TIANGONG_TLE).unwrap();
+    assert_eq!(Satellite::from_tle(tle.clone(), None).unwrap().name, "CSS (TIANHE)");
+    assert_eq!(Satellite::from_tle(tle.clone(), Some(String::from("Tiangong"))).unwrap().name, "Tiangong");
+
+    let unnamed = Tle { name: None, ..tle };
+    assert_eq!(Satellite::from_tle(unnamed, None).unwrap().name, "NORAD 48274");
+}
+
+#[test]
+fn update_propagates_to_the_time() {
+    let tle = Tle::parse(TIANGONG_TLE).unwrap();
+    let expected = Sgp4::new(tle.clone()).unwrap().geodetic_at(now()).unwrap();
+    let mut satellite = Satellite::from_tle(tle, None).unwrap();
+    assert_eq!(satellite.position, None);
+
+    satellite.update(now()).unwrap();
+    let position = satellite.position.unwrap();
+    assert_eq!((position.lat, position.lon, position.alt), (expected.lat, expected.lon, expected.alt));
+    assert_eq!(position.timestamp, now().timestamp() as f64);
+    assert!(position.lat.abs() <= 41.5, "inclination bounds the latitude");
+    assert!(satellite.elements.is_some());
+    assert_eq!(satellite.history, vec![position]);
+}
+
+#[test]
+fn history_is_capped() {
+    let mut satellite = Satellite::from_tle(Tle::parse(TIANGONG_TLE).unwrap(), None).unwrap();
+    for second in 0..MAX_POS_DATA as i64 + 5 {
+        satellite.update(now() + chrono::Duration::seconds(second)).unwrap();
+    }
+
+    assert_eq!(satellite.history.len(), MAX_POS_DATA);
+    assert_eq!(satellite.history.last().copied(), satellite.position);
+}
+
+#[test]
+fn elements_come_from_celestrak_except_for_the_iss() {
+    let providers = Providers::default();
+    assert_eq!(providers.tle_url_for(ISS_NORAD_ID), providers.tle_url);
+    assert_eq!(
+        providers.tle_url_for(48274),
+        "https://celestrak.org/NORAD/elements/gp.php?CATNR=48274&FORMAT=TLE"
+    );
+}
@@ -0,0 +1,197 @@
+//! The REST endpoints of `rs_iss serve`, answered from a tracker built offline.
+
+use chrono::{DateTime, TimeZone, Utc};
+use iss_ratatui::config::Config;
+use iss_ratatui::iss::Iss;
+use iss_ratatui::orbit::Propagator;
+use iss_ratatui::passes::Observer;
+use iss_ratatui::provider::{Position, PositionSource};
+use iss_ratatui::server::{respond, ApiServer, Reply};
+use iss_ratatui::tle::{Sgp4, Tle};
+use iss_ratatui::ui::App;
+use iss_ratatui::weather::Weather;
+use serde_json::Value;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+const ISS_TLE: &str = include_str!("fixtures/iss.tle");
+const OPEN_METEO: &str = include_str!("fixtures/open_meteo.json");
+
+/// An hour after the fixture TLE's epoch.
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, 17, 13, 0, 0).unwrap()
+}
+
+/// A tracker with no worker, moved to the fixture TLE's position at [`now`], with an observer.
+fn tracker() -> (App, Iss) {
+    let sgp4 = Sgp4::new(Tle::parse(ISS_TLE).unwrap()).unwrap();
+    let geodetic = sgp4.geodetic_at(now()).unwrap();
+    let mut iss = Iss::new();
+    iss.propagator = Some(Arc::new(sgp4));
+    iss.set_position(
+        Position {
+            lat: geodetic.lat,
+            lon: geodetic.lon,
+            alt: geodetic.alt,
+            timestamp: now().timestamp() as f64,
+        },
+        PositionSource::Live,
+    );
+
+    let mut app = App::new(Config::default()).unwrap();
+    app.observer = Some(Observer {
+        name: String::from("Cambridge"),
+        lat: 52.2053,
+        lon: 0.1218,
+        elevation: 10.0,
+    });
+    app.refresh_tracks(&iss);
+    (app, iss)
+}
+
+fn json(reply: &Reply) -> Value {
+    serde_json::from_str(&reply.body).unwrap()
+}
+
+#[test]
+fn position_is_the_latest_sample() {
+    let (app, iss) = tracker();
+    let reply = respond("/position", &app, &iss);
+    assert_eq!((reply.status, reply.content_type), (200, "application/json"));
+
+    let position = json(&reply);
+    assert_eq!(position["lat"], iss.lat);
+    assert_eq!(position["lon"], iss.lon);
+    assert_eq!(position["time"], "2026-10-17T13:00:00Z");
+    assert_eq!(position["source"], "LIVE");
+    assert_eq!(position["provider"], iss.provider.name());
+    assert!(position["sunlit"].is_boolean());
+}
+
+#[test]
+fn nothing_is_served_before_it_arrives() {
+    let app = App::new(Config::default()).unwrap();
+    let iss = Iss::new();
+
+    for path in ["/position", "/crew", "/weather"] {
+        let reply = respond(path, &app, &iss);
+        assert_eq!(reply.status, 503, "{path}");
+        assert!(json(&reply)["error"].is_string());
+    }
+    assert_eq!(json(&respond("/history", &app, &iss)), Value::Array(Vec::new()));
+}
+
+#[test]
+fn history_crew_and_weather() {
+    let (app, mut iss) = tracker();
+    iss.crew = String::from("Oleg Kononenko\nNikolai Chub");
+    iss.weather = Some(Weather::from_open_meteo(OPEN_METEO).unwrap());
+
+    let history = json(&respond("/history", &app, &iss));
+    assert_eq!(history.as_array().unwrap().len(), 1);
+    assert_eq!(history[0]["lat"], iss.lat);
+    assert_eq!(json(&respond("/crew", &app, &iss)), serde_json::json!(["Oleg Kononenko", "Nikolai Chub"]));
+
+    let weather = json(&respond("/weather", &app, &iss));
+    assert_eq!(weather["lat"], iss.lat);
+    assert!(weather["description"].is_string());
+    assert!(weather["temperature"].is_number());
+}
+
+#[test]
+fn passes_need_an_observer() {
+    let (mut app, iss) = tracker();
+    let passes = json(&respond("/passes", &app, &iss));
+    assert_eq!(passes["observer"]["name"], "Cambridge");
+    assert_eq!(passes["passes"].as_array().unwrap().len(), app.passes.len());
+
+    app.observer = None;
+    assert_eq!(respond("/passes", &app, &iss).status, 404);
+}
+
+#[test]
+fn groundtrack_is_geojson() {
+    let (app, iss) = tracker();
+    let reply = respond("/groundtrack", &app, &iss);
+    assert_eq!((reply.status, reply.content_type), (200, "application/geo+json"));
+    assert_eq!(json(&reply)["type"], "FeatureCollection");
+    assert!(!json(&reply)["features"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn paths_are_matched_without_query_or_trailing_slash() {
+    let (app, iss) = tracker();
+    assert_eq!(respond("/position/?pretty=1", &app, &iss), respond("/position", &app, &iss));
+    assert_eq!(json(&respond("/", &app, &iss))["endpoints"][0], "/position");
+
+    let unknown = respond("/satellites", &app, &iss);
+    assert_eq!(unknown.status, 404);
+    assert!(json(&unknown)["error"].as_str().unwrap().contains("/groundtrack"));
+}
+
+/// Sends `request` to `server` and returns the raw response, answering it on this thread.
+fn exchange(server: &ApiServer, app: &App, iss: &Iss, request: &'static str) -> String {
+    let addr = server.local_addr().unwrap();
+    let client = thread::spawn(move || {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    });
+    server.answer_next(app, iss, Duration::from_secs(5)).unwrap();
+    client.join().unwrap()
+}
+
+#[test]
+fn answers_over_http_to_any_origin() {
+    let (app, iss) = tracker();
+    let server = ApiServer::bind("127.0.0.1:0").unwrap();
+
+    let response = exchange(
+        &server,
+        &app,
+        &iss,
+        "GET /position HTTP/1.1\r\nHost: localhost\r\nOrigin: http://example.com\r\nConnection: close\r\n\r\n",
+    );
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+    assert!(response.contains("Access-Control-Allow-Origin: *"));
+    assert!(response.contains("Content-Type: application/json"));
+    assert!(response.contains("\"source\":\"LIVE\""));
+
+    let preflight = exchange(
+        &server,
+        &app,
+        &iss,
+        "OPTIONS /position HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+    );
+    assert!(preflight.starts_with("HTTP/1.1 204"), "{preflight}");
+    assert!(preflight.contains("Access-Control-Allow-Methods: GET, OPTIONS"));
+}
+
+#[test]
+fn a_client_hanging_up_does_not_stop_the_server() {
+    let (app, mut iss) = tracker();
+    // A history longer than the socket buffers, so writing the reply fails once the client is gone.
+    let position = *iss.pos_data.last().unwrap();
+    iss.pos_data = vec![position; 100_000];
+    let server = ApiServer::bind("127.0.0.1:0").unwrap();
+
+    let mut stream = TcpStream::connect(server.local_addr().unwrap()).unwrap();
+    stream
+        .write_all(b"GET /history HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .unwrap();
+    drop(stream);
+    server.answer_next(&app, &iss, Duration::from_secs(5)).unwrap();
+
+    let response = exchange(
+        &server,
+        &app,
+        &iss,
+        "GET /position HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+    );
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+}
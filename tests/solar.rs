@@ -0,0 +1,108 @@
+//! Where the Sun is overhead, where day meets night, and whether a point is in the Earth's shadow.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use iss_ratatui::orbit::{geodetic_to_ecef, Geodetic};
+use iss_ratatui::solar::{is_sunlit, subsolar_point, sun_elevation, terminator};
+
+/// The June 2024 solstice.
+fn solstice() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 6, 20, 20, 51, 0).unwrap()
+}
+
+#[test]
+fn sun_is_over_the_tropic_of_cancer_at_the_june_solstice() {
+    let (lat, _) = subsolar_point(solstice());
+    assert!((lat - 23.44).abs() < 0.05, "{lat}");
+}
+
+#[test]
+fn sun_is_over_the_equator_and_greenwich_near_noon_at_the_march_equinox() {
+    // Greenwich apparent noon falls about 7 minutes after 12:00 UTC in late March.
+    let (lat, lon) = subsolar_point(Utc.with_ymd_and_hms(2024, 3, 20, 12, 7, 0).unwrap());
+    assert!(lat.abs() < 0.5, "{lat}");
+    assert!(lon.abs() < 0.5, "{lon}");
+}
+
+#[test]
+fn sun_is_overhead_at_the_subsolar_point() {
+    let (lat, lon) = subsolar_point(solstice());
+    assert!((sun_elevation(lat, lon, solstice()) - 90.0).abs() < 0.01);
+}
+
+#[test]
+fn sun_is_on_the_horizon_along_the_terminator() {
+    let line = terminator(solstice());
+
+    assert_eq!(line.first().unwrap().0, -180.0);
+    assert_eq!(line.last().unwrap().0, 180.0);
+    for (lon, lat) in line {
+        let elevation = sun_elevation(lat, lon, solstice());
+        assert!(elevation.abs() < 0.01, "{elevation}° at ({lat}, {lon})");
+    }
+}
+
+/// The March 2024 equinox, to the millisecond: when the subsolar point crosses the equator.
+fn equinox() -> DateTime<Utc> {
+    let (mut before, mut after) = (
+        Utc.with_ymd_and_hms(2024, 3, 19, 0, 0, 0).unwrap(),
+        Utc.with_ymd_and_hms(2024, 3, 21, 0, 0, 0).unwrap(),
+    );
+    while after - before > Duration::milliseconds(1) {
+        let middle = before + (after - before) / 2;
+        if subsolar_point(middle).0 < 0.0 {
+            before = middle;
+        } else {
+            after = middle;
+        }
+    }
+    after
+}
+
+#[test]
+fn terminator_runs_along_the_meridians_at_the_equinox() {
+    let time = equinox();
+    assert!(time > Utc.with_ymd_and_hms(2024, 3, 20, 2, 0, 0).unwrap(), "{time}");
+    assert!(time < Utc.with_ymd_and_hms(2024, 3, 20, 4, 0, 0).unwrap(), "{time}");
+    let (sun_lat, sun_lon) = subsolar_point(time);
+    assert!(sun_lat.to_radians().abs() < 1e-9, "{sun_lat}");
+
+    let line = terminator(time);
+    assert!(line.iter().all(|&(lon, lat)| lon.is_finite() && (-90.0..=90.0).contains(&lat)), "{line:?}");
+    let (west, east) = (line[0].0, line[line.len() - 1].0);
+    assert!(west < east);
+    for meridian in [west, east] {
+        let from_the_sun = (meridian - sun_lon).rem_euclid(360.0);
+        assert!((from_the_sun - 90.0).abs() < 1e-9 || (from_the_sun - 270.0).abs() < 1e-9, "{meridian}");
+        for lat in [-60.0, 0.0, 45.0] {
+            let elevation = sun_elevation(lat, meridian, time);
+            assert!(elevation.abs() < 0.01, "{elevation}° at ({lat}, {meridian})");
+        }
+    }
+
+    // A second either side, the line still follows the horizon.
+    for time in [time - Duration::seconds(1), time + Duration::seconds(1)] {
+        let line = terminator(time);
+        assert!(line.iter().all(|&(_, lat)| lat.is_finite()), "{line:?}");
+    }
+}
+
+#[test]
+fn iss_over_the_night_side_is_in_shadow_and_lit_above_the_terminator() {
+    let time = solstice();
+    let (lat, lon) = subsolar_point(time);
+    let ecef = |lat: f64, lon: f64| geodetic_to_ecef(&Geodetic { lat, lon, alt: 420.0 });
+
+    assert!(is_sunlit(&ecef(lat, lon), time));
+    assert!(!is_sunlit(&ecef(-lat, lon + 180.0), time));
+    // Still catching the Sun just past the terminator, as seen at dusk.
+    let (dusk_lon, dusk_lat) = terminator(time)[60];
+    assert!(is_sunlit(&ecef(dusk_lat, dusk_lon), time));
+}
+
+#[test]
+fn subsolar_point_moves_west_with_the_day() {
+    let (_, before) = subsolar_point(solstice());
+    let (_, after) = subsolar_point(solstice() + Duration::hours(1));
+    let moved = (before - after).rem_euclid(360.0);
+    assert!((moved - 15.0).abs() < 0.1, "{moved}");
+}
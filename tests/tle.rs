@@ -0,0 +1,98 @@
+//! TLE parsing, and SGP4 against the verification cases of Vallado et al., "Revisiting
+//! Spacetrack Report #3" (AIAA 2006-6753), whose reference output is in TEME km and km/s.
+
+use iss_ratatui::error::IssError;
+use iss_ratatui::tle::{Sgp4, Tle};
+
+/// Vanguard 1: eccentric, and high enough for the full drag terms.
+const VANGUARD: &str = "\
+1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753
+2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667";
+
+/// A Delta 1 debris fragment in a near-circular low orbit.
+const DELTA_DEBRIS: &str = "\
+1 06251U 62025E   06176.82412014  .00008885  00000-0  12808-3 0  3985
+2 06251  58.0579  54.0425 0030035 139.1568 221.1854 15.56387291  6774";
+
+const ISS_TLE: &str = include_str!("fixtures/iss.tle");
+
+fn assert_close(actual: [f64; 3], expected: [f64; 3], tolerance: f64, what: &str) {
+    for axis in 0..3 {
+        assert!(
+            (actual[axis] - expected[axis]).abs() < tolerance,
+            "{what}: {actual:?} != {expected:?}"
+        );
+    }
+}
+
+#[test]
+fn vanguard_matches_the_reference_output() {
+    let sgp4 = Sgp4::new(Tle::parse(VANGUARD).unwrap()).unwrap();
+    let expected = [
+        (0.0, [7022.46529266, -1400.08296755, 0.03995155], [1.893841015, 6.405893759, 4.534807250]),
+        (360.0, [-7154.03120202, -3783.17682504, -3536.19412294], [4.741887409, -4.151817765, -2.093935425]),
+        (720.0, [-7134.59340119, 6531.68641334, 3260.27186483], [-4.113793027, -2.911922039, -2.557327851]),
+        (1080.0, [5568.53901181, 4492.06992591, 3863.87641983], [-4.209106476, 5.159719888, 2.744852980]),
+        (1440.0, [-938.55923943, -6268.18748831, -4294.02924751], [7.536105209, -0.427127707, 0.989878080]),
+        (4320.0, [-9060.47373569, 4658.70952502, 813.68673153], [-2.232832783, -4.110453490, -3.157345433]),
+    ];
+
+    for (minutes, position, velocity) in expected {
+        let (r, v) = sgp4.propagate(minutes).unwrap();
+        assert_close(r, position, 1e-5, &format!("position at {minutes} min"));
+        assert_close(v, velocity, 1e-8, &format!("velocity at {minutes} min"));
+    }
+}
+
+#[test]
+fn low_orbit_debris_matches_the_reference_output() {
+    let sgp4 = Sgp4::new(Tle::parse(DELTA_DEBRIS).unwrap()).unwrap();
+
+    let (r, v) = sgp4.propagate(0.0).unwrap();
+    assert_close(r, [3988.31022699, 5498.96657235, 0.90055879], 1e-5, "position at epoch");
+    assert_close(v, [-3.290032738, 2.357652820, 6.496623475], 1e-8, "velocity at epoch");
+    let (r, _) = sgp4.propagate(120.0).unwrap();
+    assert_close(r, [-3935.69800083, 409.10980837, 5471.33577327], 1e-5, "position at 120 min");
+}
+
+#[test]
+fn parses_the_fields() {
+    let tle = Tle::parse(VANGUARD).unwrap();
+    assert_eq!(tle.name, None);
+    assert_eq!(tle.norad_id, 5);
+    assert_eq!(tle.epoch.format("%Y-%m-%d %H:%M:%S").to_string(), "2000-06-27 18:50:19");
+    assert!((tle.bstar - 2.8098e-5).abs() < 1e-12);
+    assert!((tle.eccentricity - 0.1859667).abs() < 1e-12);
+    assert!((tle.mean_motion - 10.82419157).abs() < 1e-12);
+
+    assert_eq!(Tle::parse(ISS_TLE).unwrap().name.as_deref(), Some("ISS (ZARYA)"));
+}
+
+#[test]
+fn rejects_malformed_element_sets() {
+    let (line1, line2) = VANGUARD.split_once('\n').unwrap();
+    let bad_checksum = format!("{line1}\n{}8", &line2[..68]);
+    let short = format!("{line1}\n{}", &line2[..60]);
+    // Two bytes across the end of the checksummed columns, so slicing there would split it.
+    let non_ascii = format!("{}é{}\n{line2}", &line1[..67], &line1[68..]);
+
+    for (text, what) in [
+        (bad_checksum.as_str(), "bad checksum"),
+        (line1, "missing line 2"),
+        (short.as_str(), "short line"),
+        (non_ascii.as_str(), "non-ASCII line"),
+        (line2, "missing line 1"),
+        ("", "empty"),
+    ] {
+        assert!(matches!(Tle::parse(text), Err(IssError::InvalidTle(_))), "{what}");
+    }
+}
+
+#[test]
+fn deep_space_orbits_are_rejected() {
+    let geostationary = "\
+1 26038U 00011A   06176.54437592 -.00000262  00000-0  10000-3 0  4765
+2 26038   0.0254 265.7587 0002591 176.6519 110.5540  1.00271436 23043";
+    let tle = Tle::parse(geostationary).unwrap();
+    assert!(matches!(Sgp4::new(tle), Err(IssError::Propagation(_))));
+}
@@ -0,0 +1,697 @@
+//! The TUI rendered into a `TestBackend` from fixed data, and driven by scripted key presses.
+//!
+//! Frames are compared against the text in `tests/snapshots`. After an intended change to the
+//! layout, rerun with `UPDATE_SNAPSHOTS=1` and review the diff.
+
+use chrono::{DateTime, Duration, FixedOffset, TimeZone, Utc};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
+use iss_ratatui::config::{Config, Keys};
+use iss_ratatui::error::IssError;
+use iss_ratatui::footprint::Footprint;
+use iss_ratatui::groundtrack::{predict_positions, GroundTrack};
+use iss_ratatui::http::HttpClient;
+use iss_ratatui::iss::{nearest_place, Crossing, Iss, NearestPlace};
+use iss_ratatui::orbit::{OrbitalElements, Propagator, StateVector, MU_EARTH};
+use iss_ratatui::passes::{predict_passes, Observer, PASS_MIN_ELEVATION, PASS_PREDICTION_HOURS};
+use iss_ratatui::provider::{Position, PositionSource, ProviderKind};
+use iss_ratatui::recorder::{Replay, Sample};
+use iss_ratatui::satellite::{Satellite, ISS_NORAD_ID};
+use iss_ratatui::solar::{is_sunlit, subsolar_point, terminator};
+use iss_ratatui::tle::{Sgp4, Tle};
+use iss_ratatui::ui::{
+    run_app, ui, App, CurrentScreen, FeedStatus, Palette, SatelliteView, ScriptedEvents, ViewModel,
+};
+use iss_ratatui::weather::Weather;
+use iss_ratatui::worker::{Endpoints, Worker};
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::style::Color;
+use ratatui::Terminal;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const ISS_TLE: &str = include_str!("fixtures/iss.tle");
+const TIANGONG_TLE: &str = include_str!("fixtures/tiangong.tle");
+const OPEN_METEO: &str = include_str!("fixtures/open_meteo.json");
+
+const SIZES: [(u16, u16); 3] = [(80, 24), (120, 40), (160, 50)];
+
+/// An hour after the fixture TLE's epoch.
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, 17, 13, 0, 0).unwrap()
+}
+
+/// Everything a frame can show, fixed: the position, tracks, passes, elements and daylight all
+/// come from the fixture TLE at [`now`], with Tiangong tracked alongside.
+fn view(screen: CurrentScreen) -> ViewModel {
+    let sgp4 = Sgp4::new(Tle::parse(ISS_TLE).unwrap()).unwrap();
+    let geodetic = sgp4.geodetic_at(now()).unwrap();
+    let round = |value: f64| (value * 1e4).round() / 1e4;
+    let tiangong = Sgp4::new(Tle::parse(TIANGONG_TLE).unwrap()).unwrap();
+    let tiangong_at = tiangong.geodetic_at(now()).unwrap();
+    let observer = Observer {
+        name: String::from("Cambridge"),
+        lat: 52.2053,
+        lon: 0.1218,
+        elevation: 10.0,
+    };
+
+    ViewModel {
+        screen,
+        name: String::from("ISS"),
+        utc: now(),
+        local: now().with_timezone(&FixedOffset::east_opt(3600).unwrap()),
+        elapsed: Duration::seconds(754),
+        lat: round(geodetic.lat),
+        lon: round(geodetic.lon),
+        alt: round(geodetic.alt),
+        country: String::from("Atlantic Ocean"),
+        nearest_place: Some(NearestPlace {
+            name: String::from("Walvis Bay"),
+            country: String::from("NA"),
+            distance_km: 1234.5,
+            bearing: 75.0,
+        }),
+        sunlit: is_sunlit(&sgp4.ecef_at(now()).unwrap(), now()),
+        subsolar: subsolar_point(now()),
+        terminator: terminator(now()),
+        horizon: Footprint::new(round(geodetic.lat), round(geodetic.lon), round(geodetic.alt), 0.0),
+        footprint: Footprint::new(round(geodetic.lat), round(geodetic.lon), round(geodetic.alt), 10.0),
+        footprint_countries: vec![String::from("Angola"), String::from("Namibia"), String::from("South Africa")],
+        alt_perigee_apogee: String::from("Approaching Apogee"),
+        elements: Some(OrbitalElements::from_state(&sgp4.state_at(now()).unwrap())),
+        cross_check_km: Some(1.25),
+        provider: String::from("wheretheiss.at"),
+        source: PositionSource::Live,
+        replay_speed: None,
+        position_feed: FeedStatus::Fresh,
+        crew_feed: FeedStatus::Fresh,
+        weather_feed: FeedStatus::Fresh,
+        status: None,
+        notice: None,
+        zoom: 50.0,
+        ground_track: GroundTrack::predict(&sgp4, now(), 2.0),
+        past_track: GroundTrack::default(),
+        passes: predict_passes(&sgp4, &observer, now(), PASS_PREDICTION_HOURS, PASS_MIN_ELEVATION),
+        observer: Some(observer),
+        satellites: vec![
+            SatelliteView {
+                norad_id: ISS_NORAD_ID,
+                name: String::from("ISS"),
+                position: Some(Position {
+                    lat: round(geodetic.lat),
+                    lon: round(geodetic.lon),
+                    alt: round(geodetic.alt),
+                    timestamp: now().timestamp() as f64,
+                }),
+                country: String::from("Ocean"),
+                color: Color::Red,
+                focused: true,
+                track: GroundTrack::default(),
+            },
+            SatelliteView {
+                norad_id: 48274,
+                name: String::from("CSS (TIANHE)"),
+                position: Some(Position {
+                    lat: round(tiangong_at.lat),
+                    lon: round(tiangong_at.lon),
+                    alt: round(tiangong_at.alt),
+                    timestamp: now().timestamp() as f64,
+                }),
+                country: String::from("Unknown Country"),
+                color: Color::Magenta,
+                focused: false,
+                track: GroundTrack::from_history(&predict_positions(&tiangong, now() - Duration::minutes(60), 0.6)),
+            },
+        ],
+        selected: 1,
+        prompt: None,
+        crossings: crossings(),
+        events_scroll: 0,
+        future_trajectories: String::from("2026-10-17T13:00:00.000 -4527.1 1985.3 4461.9\n2026-10-17T13:04:00.000 -3311.8 3412.0 4935.2"),
+        crew: String::from("Oleg Kononenko\nNikolai Chub\nTracy Caldwell Dyson"),
+        weather: Some(Weather::from_open_meteo(OPEN_METEO).unwrap()),
+        keys: Keys::default(),
+        palette: Palette::from_theme(&Config::default().theme).unwrap(),
+    }
+}
+
+/// The fixture's last three border crossings, oldest first.
+fn crossings() -> Vec<Crossing> {
+    let crossing = |minutes: i64, from: &str, to: &str| Crossing {
+        time: (now() - Duration::minutes(minutes)).timestamp() as f64,
+        from: String::from(from),
+        to: String::from(to),
+    };
+    vec![
+        crossing(31, "Pacific Ocean", "Peru"),
+        crossing(27, "Peru", "Brazil"),
+        crossing(14, "Brazil", "Atlantic Ocean"),
+    ]
+}
+
+fn render(view: &ViewModel, width: u16, height: u16) -> Buffer {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|f| ui(f, view)).unwrap();
+    terminal.backend().buffer().clone()
+}
+
+/// The characters of `buffer`, one line per row, without trailing blanks.
+fn text(buffer: &Buffer) -> String {
+    let width = buffer.area.width as usize;
+    let symbols: Vec<&str> = buffer.content.iter().map(|cell| cell.symbol()).collect();
+    symbols
+        .chunks(width)
+        .map(|row| row.concat().trim_end().to_string() + "\n")
+        .collect()
+}
+
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{name}.txt"));
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("no snapshot at {}; run with UPDATE_SNAPSHOTS=1", path.display()));
+    assert!(
+        expected == actual,
+        "{name} differs from {}:\n--- expected\n{expected}\n--- actual\n{actual}",
+        path.display()
+    );
+}
+
+fn assert_screen(screen: CurrentScreen, name: &str) {
+    let view = view(screen);
+    for (width, height) in SIZES {
+        assert_snapshot(&format!("{name}_{width}x{height}"), &text(&render(&view, width, height)));
+    }
+}
+
+/// Where `needle` first appears in `buffer`, as (x, y).
+fn find(buffer: &Buffer, needle: &str) -> Option<(u16, u16)> {
+    let lines = text(buffer);
+    lines.lines().enumerate().find_map(|(y, line)| {
+        let byte = line.find(needle)?;
+        Some((line[..byte].chars().count() as u16, y as u16))
+    })
+}
+
+#[test]
+fn tracker() {
+    assert_screen(CurrentScreen::Tracker, "tracker");
+}
+
+#[test]
+fn full_map() {
+    assert_screen(CurrentScreen::FullMap, "full_map");
+}
+
+#[test]
+fn passes() {
+    assert_screen(CurrentScreen::Passes, "passes");
+}
+
+#[test]
+fn crew() {
+    assert_screen(CurrentScreen::Crew, "crew");
+}
+
+#[test]
+fn exiting() {
+    assert_screen(CurrentScreen::Exiting, "exiting");
+}
+
+#[test]
+fn satellites() {
+    assert_screen(CurrentScreen::Satellites, "satellites");
+}
+
+#[test]
+fn events() {
+    assert_screen(CurrentScreen::Events, "events");
+}
+
+#[test]
+fn adding_a_satellite() {
+    let view = ViewModel {
+        prompt: Some(String::from("2058")),
+        ..view(CurrentScreen::Satellites)
+    };
+
+    assert_snapshot("satellites_prompt_80x24", &text(&render(&view, 80, 24)));
+}
+
+#[test]
+fn tracker_replaying_with_stale_feeds() {
+    let view = ViewModel {
+        source: PositionSource::Propagated,
+        replay_speed: Some(8.0),
+        position_feed: FeedStatus::Stale(17),
+        crew_feed: FeedStatus::Failed,
+        weather_feed: FeedStatus::Loading,
+        status: Some(String::from("HTTP 503 from https://api.wheretheiss.at (at 14:00:00)")),
+        ..view(CurrentScreen::Tracker)
+    };
+
+    assert_snapshot("tracker_replay_120x40", &text(&render(&view, 120, 40)));
+}
+
+#[test]
+fn theme_colors_the_titles() {
+    let mut theme = Config::default().theme;
+    theme.title = String::from("#ff8800");
+    let view = ViewModel {
+        palette: Palette::from_theme(&theme).unwrap(),
+        ..view(CurrentScreen::Tracker)
+    };
+
+    let buffer = render(&view, 120, 40);
+    let (x, y) = find(&buffer, "ISS Tracker").unwrap();
+    assert_eq!(buffer.get(x, y).fg, Color::Rgb(0xff, 0x88, 0x00));
+}
+
+#[test]
+fn map_shows_the_sun_and_whether_the_iss_is_lit() {
+    let view = ViewModel {
+        zoom: 180.0,
+        ..view(CurrentScreen::Tracker)
+    };
+    let buffer = render(&view, 160, 50);
+    let (x, y) = find(&buffer, "☀").unwrap();
+    assert_eq!(buffer.get(x, y).fg, view.palette.sun);
+
+    let expected = if view.sunlit { "In sunlight" } else { "In Earth's shadow" };
+    assert!(find(&buffer, expected).is_some());
+    let shadowed = ViewModel {
+        sunlit: !view.sunlit,
+        ..view.clone()
+    };
+    let other = if view.sunlit { "In Earth's shadow" } else { "In sunlight" };
+    assert!(find(&render(&shadowed, 160, 50), other).is_some());
+}
+
+#[test]
+fn additional_info_counts_down_to_perigee_and_apogee() {
+    // a = 7000 km and e = 0.05, a quarter of the way round from perigee.
+    let speed = (MU_EARTH / 6982.5).sqrt();
+    let state = StateVector {
+        epoch: now(),
+        position: [0.0, 6982.5, 0.0],
+        velocity: [-speed, 0.05 * speed, 0.0],
+    };
+    let view = ViewModel {
+        elements: Some(OrbitalElements::from_state(&state)),
+        ..view(CurrentScreen::Tracker)
+    };
+    let buffer = render(&view, 160, 50);
+
+    assert!(find(&buffer, "Perigee 271.9 km in 74m 24s").is_some(), "{}", text(&buffer));
+    assert!(find(&buffer, "Apogee 971.9 km in 25m 49s").is_some(), "{}", text(&buffer));
+    assert!(find(&buffer, "a 7000.0 km  e 0.05000").is_some(), "{}", text(&buffer));
+}
+
+#[test]
+fn map_shows_every_tracked_satellite() {
+    let view = ViewModel {
+        zoom: 180.0,
+        ..view(CurrentScreen::FullMap)
+    };
+    let buffer = render(&view, 160, 50);
+
+    let (x, y) = find(&buffer, "CSS (TIANHE)").unwrap();
+    assert_eq!(buffer.get(x, y).fg, Color::Magenta);
+}
+
+/// An app with no worker, so nothing happens that the script does not ask for.
+fn app() -> (Terminal<TestBackend>, App, Iss) {
+    let terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    (terminal, App::new(Config::default()).unwrap(), Iss::new())
+}
+
+fn run(app: &mut App, iss: &mut Iss, terminal: &mut Terminal<TestBackend>, events: &mut ScriptedEvents) -> bool {
+    run_app(terminal, app, iss, events, chrono::Local::now()).unwrap()
+}
+
+#[test]
+fn next_screen_cycles_through_every_screen() {
+    let (mut terminal, mut app, mut iss) = app();
+    let mut seen = Vec::new();
+
+    for _ in 0..6 {
+        let _ = run_app(&mut terminal, &mut app, &mut iss, &mut ScriptedEvents::keys("l"), chrono::Local::now());
+        seen.push(app.current_screen);
+    }
+    assert_eq!(
+        seen,
+        [
+            CurrentScreen::FullMap,
+            CurrentScreen::Passes,
+            CurrentScreen::Crew,
+            CurrentScreen::Satellites,
+            CurrentScreen::Events,
+            CurrentScreen::Tracker,
+        ]
+    );
+}
+
+#[test]
+fn quitting_asks_for_confirmation() {
+    let (mut terminal, mut app, mut iss) = app();
+
+    assert!(run(&mut app, &mut iss, &mut terminal, &mut ScriptedEvents::keys("lqy")));
+    assert!(find(terminal.backend().buffer(), "Would you like to quit? (y/n)").is_some());
+}
+
+#[test]
+fn declining_to_quit_returns_to_the_tracker() {
+    let (mut terminal, mut app, mut iss) = app();
+
+    // Runs off the end of the script instead of quitting.
+    let result = run_app(&mut terminal, &mut app, &mut iss, &mut ScriptedEvents::keys("lqn"), chrono::Local::now());
+    assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::UnexpectedEof);
+    assert_eq!(app.current_screen, CurrentScreen::Tracker);
+
+    let _ = run_app(&mut terminal, &mut app, &mut iss, &mut ScriptedEvents::keys("qq"), chrono::Local::now());
+    assert_eq!(app.current_screen, CurrentScreen::Tracker);
+}
+
+#[test]
+fn zoom_keys_only_work_where_the_map_is() {
+    let (mut terminal, mut app, mut iss) = app();
+
+    // Two in and one out on the Tracker, then one in on the Passes screen, which is ignored.
+    let _ = run_app(&mut terminal, &mut app, &mut iss, &mut ScriptedEvents::keys("]][ll]"), chrono::Local::now());
+    assert_eq!(app.current_screen, CurrentScreen::Passes);
+    assert_eq!(app.zoom, 40.0);
+}
+
+#[test]
+fn rebound_keys_are_used() {
+    let mut config = Config::default();
+    config.keys.next_screen = 'n';
+    config.keys.quit = 'x';
+    // Wide enough for the whole footer.
+    let mut terminal = Terminal::new(TestBackend::new(160, 50)).unwrap();
+    let (mut app, mut iss) = (App::new(config).unwrap(), Iss::new());
+
+    // 'l' is no longer bound to anything.
+    let _ = run_app(&mut terminal, &mut app, &mut iss, &mut ScriptedEvents::keys("nl"), chrono::Local::now());
+    assert_eq!(app.current_screen, CurrentScreen::FullMap);
+    assert!(find(terminal.backend().buffer(), "VIEWS: 'n'").is_some());
+    assert!(find(terminal.backend().buffer(), "QUIT: 'x'").is_some());
+
+    assert!(run(&mut app, &mut iss, &mut terminal, &mut ScriptedEvents::keys("xy")));
+}
+
+#[test]
+fn key_releases_are_ignored() {
+    let (mut terminal, mut app, mut iss) = app();
+    let release = KeyEvent {
+        code: KeyCode::Char('l'),
+        modifiers: KeyModifiers::NONE,
+        kind: KeyEventKind::Release,
+        state: KeyEventState::NONE,
+    };
+
+    let _ = run_app(
+        &mut terminal,
+        &mut app,
+        &mut iss,
+        &mut ScriptedEvents::new([Some(Event::Key(release))]),
+        chrono::Local::now(),
+    );
+    assert_eq!(app.current_screen, CurrentScreen::Tracker);
+}
+
+fn samples() -> Vec<Sample> {
+    let sample = |minutes: i64, lat: f64, lon: f64| Sample {
+        time: now() + Duration::minutes(minutes),
+        lat,
+        lon,
+        alt: 415.0,
+        country: String::from("Ocean"),
+        source: PositionSource::Live,
+    };
+    vec![sample(0, 12.5, -40.25), sample(60, 30.0, 10.0)]
+}
+
+#[test]
+fn update_key_steps_the_replay() {
+    let (mut terminal, mut app, mut iss) = app();
+    app.replay = Some(Replay::new(samples(), 1.0).unwrap());
+
+    let _ = run_app(&mut terminal, &mut app, &mut iss, &mut ScriptedEvents::keys("u"), chrono::Local::now());
+    assert_eq!((iss.lat, iss.lon), (12.5, -40.25));
+    // The recorded region, not the Atlantic Ocean a new lookup would find.
+    assert_eq!(iss.country, "Ocean");
+    assert!(find(terminal.backend().buffer(), "REPLAY 1x").is_some());
+}
+
+#[test]
+fn frames_show_what_was_worked_out_when_the_position_arrived() {
+    let (mut terminal, mut app, mut iss) = app();
+    app.replay = Some(Replay::new(samples(), 1.0).unwrap());
+    assert_eq!(app.view_model(&iss, Duration::zero()).nearest_place, None);
+
+    let _ = run_app(&mut terminal, &mut app, &mut iss, &mut ScriptedEvents::keys("u"), chrono::Local::now());
+    let view = app.view_model(&iss, Duration::zero());
+    assert_eq!(view.nearest_place, nearest_place(12.5, -40.25));
+    assert_eq!(view.nearest_place, app.nearest_place);
+    assert_eq!(view.terminator, app.terminator);
+
+    // Frames in between show the same, until the next position arrives.
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    assert_eq!(app.view_model(&iss, Duration::seconds(1)).terminator, view.terminator);
+}
+
+#[test]
+fn replay_speed_keys_double_and_halve() {
+    let (mut terminal, mut app, mut iss) = app();
+    app.replay = Some(Replay::new(samples(), 1.0).unwrap());
+
+    let _ = run_app(&mut terminal, &mut app, &mut iss, &mut ScriptedEvents::keys(">>><"), chrono::Local::now());
+    assert_eq!(app.replay.as_ref().unwrap().speed, 4.0);
+}
+
+#[test]
+fn idle_polls_drive_the_replay() {
+    let mut config = Config::default();
+    config.intervals.refresh_ms = 500;
+    config.intervals.poll_ms = 250;
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    let (mut app, mut iss) = (App::new(config).unwrap(), Iss::new());
+    app.replay = Some(Replay::new(samples(), 1.0).unwrap());
+
+    // One timed-out poll is not yet a refresh; the second is.
+    let _ = run_app(&mut terminal, &mut app, &mut iss, &mut ScriptedEvents::new([None]), chrono::Local::now());
+    assert!(iss.pos_data.is_empty());
+    let _ = run_app(&mut terminal, &mut app, &mut iss, &mut ScriptedEvents::new([None, None]), chrono::Local::now());
+    assert_eq!(iss.pos_data.len(), 1);
+}
+
+#[test]
+fn passes_during_a_replay_are_predicted_from_the_recording() {
+    let (mut terminal, mut app, mut iss) = app();
+    let sgp4 = Sgp4::new(Tle::parse(ISS_TLE).unwrap()).unwrap();
+    let observer = Observer {
+        name: String::from("Cambridge"),
+        lat: 52.2053,
+        lon: 0.1218,
+        elevation: 10.0,
+    };
+    let expected = predict_passes(&sgp4, &observer, now(), PASS_PREDICTION_HOURS, PASS_MIN_ELEVATION);
+    iss.propagator = Some(Arc::new(sgp4));
+    app.observer = Some(observer);
+    app.replay = Some(Replay::new(samples(), 1.0).unwrap());
+
+    let _ = run_app(&mut terminal, &mut app, &mut iss, &mut ScriptedEvents::keys("u"), chrono::Local::now());
+    // The replay clock has moved on by the time the passes are predicted, but only just.
+    let (rise, expected) = (app.passes.first().unwrap().rise, expected.first().unwrap().rise);
+    assert!((rise - expected).num_seconds().abs() < 1, "{rise} against {expected}");
+}
+
+#[test]
+fn the_flown_track_is_the_last_93_minutes_however_often_it_was_sampled() {
+    let (mut terminal, mut app, mut iss) = app();
+    // A position a minute for three hours, though the app refreshes every few seconds.
+    iss.pos_data = (0..180)
+        .map(|minute| Position {
+            lat: 0.0,
+            lon: minute as f64 * 0.5,
+            alt: 415.0,
+            timestamp: (now() + Duration::minutes(minute)).timestamp() as f64,
+        })
+        .collect();
+    let sample = Sample {
+        time: now() + Duration::minutes(180),
+        ..samples().remove(0)
+    };
+    app.replay = Some(Replay::new(vec![sample], 1.0).unwrap());
+
+    let _ = run_app(&mut terminal, &mut app, &mut iss, &mut ScriptedEvents::keys("u"), chrono::Local::now());
+    let points: usize = app.iss_track.segments.iter().map(Vec::len).sum();
+    // Minutes 87 to 179, and the replayed sample at 180.
+    assert_eq!(points, 94);
+}
+
+fn press(code: KeyCode) -> Option<Event> {
+    Some(Event::Key(KeyEvent::from(code)))
+}
+
+/// An app on the Satellites screen, tracking Tiangong as well as the ISS.
+fn satellites_app() -> (Terminal<TestBackend>, App, Iss) {
+    let (terminal, mut app, iss) = app();
+    app.current_screen = CurrentScreen::Satellites;
+    app.add_satellite(Satellite::from_tle(Tle::parse(TIANGONG_TLE).unwrap(), None).unwrap(), None);
+    (terminal, app, iss)
+}
+
+#[test]
+fn enter_follows_the_selected_satellite() {
+    let (mut terminal, mut app, mut iss) = satellites_app();
+
+    // The selection stops at the last row.
+    let mut events = ScriptedEvents::new([press(KeyCode::Down), press(KeyCode::Down), press(KeyCode::Enter)]);
+    let _ = run_app(&mut terminal, &mut app, &mut iss, &mut events, chrono::Local::now());
+    assert_eq!((app.selected, app.focus), (1, 48274));
+    assert!(!app.ground_track.segments.is_empty());
+    let view = app.view_model(&iss, Duration::zero());
+    assert_eq!(view.name, "CSS (TIANHE)");
+    assert_eq!(view.source, PositionSource::Propagated);
+    assert_eq!(view.satellites[1].color, Color::Magenta);
+    assert!(view.satellites[1].focused);
+
+    let mut events = ScriptedEvents::new([press(KeyCode::Up), press(KeyCode::Enter)]);
+    let _ = run_app(&mut terminal, &mut app, &mut iss, &mut events, chrono::Local::now());
+    assert_eq!(app.focus, ISS_NORAD_ID);
+    assert_eq!(app.view_model(&iss, Duration::zero()).name, "ISS");
+}
+
+#[test]
+fn labels_follow_the_focused_satellite() {
+    let (_, mut app, mut iss) = satellites_app();
+    iss.crew = String::from("Oleg Kononenko");
+    app.focus_on(1, &iss);
+    let view = app.view_model(&iss, Duration::zero());
+
+    let tracker = text(&render(&ViewModel { screen: CurrentScreen::Tracker, ..view.clone() }, 160, 50));
+    assert!(tracker.contains("CSS (TIANHE) Tracker"), "{tracker}");
+    assert!(tracker.contains("CSS (TIANHE) Time:"), "{tracker}");
+    assert!(!tracker.contains("ISS Time:"), "{tracker}");
+
+    let crew = text(&render(&ViewModel { screen: CurrentScreen::Crew, ..view }, 160, 50));
+    assert!(crew.contains("Current CSS (TIANHE) Crew"), "{crew}");
+    assert!(crew.contains("No crew list is available for CSS (TIANHE)"), "{crew}");
+    assert!(!crew.contains("Oleg Kononenko"), "{crew}");
+}
+
+/// Answers every request with an error, as an unreachable server would.
+#[derive(Debug)]
+struct Unavailable;
+
+impl HttpClient for Unavailable {
+    fn get(&self, url: &str) -> Result<String, IssError> {
+        Err(IssError::HttpStatus {
+            url: url.to_string(),
+            status: 503,
+        })
+    }
+}
+
+#[test]
+fn a_satellite_being_downloaded_is_not_asked_for_again() {
+    let (_, mut app, mut iss) = app();
+    let schedule = app.config.intervals.schedule();
+    app.worker = Some(Worker::spawn(None, None, schedule, Endpoints::default(), Arc::new(Unavailable)).unwrap());
+
+    app.track(48274);
+    app.track(48274);
+    assert_eq!(app.downloading, [48274]);
+    assert_eq!(app.notice.as_deref(), Some("Already downloading the elements of NORAD 48274"));
+
+    // Once the download has failed, the satellite can be asked for again.
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while !app.downloading.is_empty() && std::time::Instant::now() < deadline {
+        app.poll_worker(&mut iss);
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    assert!(app.downloading.is_empty());
+    assert!(app.satellites.is_empty());
+    app.track(48274);
+    assert_eq!(app.downloading, [48274]);
+}
+
+/// Serves the ISS fixture TLE and nothing else.
+#[derive(Debug)]
+struct TleOnly;
+
+impl HttpClient for TleOnly {
+    fn get(&self, url: &str) -> Result<String, IssError> {
+        match url {
+            "tle" => Ok(ISS_TLE.to_string()),
+            _ => Unavailable.get(url),
+        }
+    }
+}
+
+#[test]
+fn downloaded_elements_are_installed_when_they_arrive() {
+    let (_, mut app, mut iss) = app();
+    app.config.providers.default = ProviderKind::Tle;
+    let schedule = app.config.intervals.schedule();
+    app.worker = Some(Worker::spawn(None, None, schedule, Endpoints::default(), Arc::new(TleOnly)).unwrap());
+    assert!(iss.propagator.is_none());
+
+    app.worker.as_ref().unwrap().fetch_iss_tle(String::from("tle"));
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while iss.propagator.is_none() && std::time::Instant::now() < deadline {
+        app.poll_worker(&mut iss);
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    // The TLE becomes both the propagator and, as the configured provider, the position source.
+    let sgp4 = iss.propagator.as_ref().expect("the TLE arrived");
+    assert_eq!(iss.provider.name(), sgp4.name());
+    assert!(iss.cross_check.is_none());
+    assert!(!app.ground_track.segments.is_empty());
+}
+
+#[test]
+fn add_key_prompts_for_a_norad_id() {
+    let (mut terminal, mut app, mut iss) = satellites_app();
+
+    // Only digits are typed; other keys, even bound ones, are ignored until Enter or Esc.
+    let mut events = ScriptedEvents::new(
+        "a2x05q".chars().map(|key| press(KeyCode::Char(key))).chain([press(KeyCode::Backspace), press(KeyCode::Char('8'))]),
+    );
+    let _ = run_app(&mut terminal, &mut app, &mut iss, &mut events, chrono::Local::now());
+    assert_eq!(app.prompt.as_deref(), Some("208"));
+    assert_eq!(app.current_screen, CurrentScreen::Satellites);
+    assert!(find(terminal.backend().buffer(), "NORAD ID: 208_").is_some());
+
+    let _ = run_app(&mut terminal, &mut app, &mut iss, &mut ScriptedEvents::new([press(KeyCode::Esc)]), chrono::Local::now());
+    assert_eq!(app.prompt, None);
+    assert_eq!(app.satellites.len(), 1);
+}
+
+#[test]
+fn arrow_keys_scroll_the_events_stopping_at_the_oldest() {
+    let (mut terminal, mut app, mut iss) = app();
+    app.current_screen = CurrentScreen::Events;
+    iss.crossings = crossings();
+
+    let mut events = ScriptedEvents::new([press(KeyCode::Down), press(KeyCode::Down), press(KeyCode::Down)]);
+    let _ = run_app(&mut terminal, &mut app, &mut iss, &mut events, chrono::Local::now());
+    assert_eq!(app.events_scroll, 2);
+    assert!(find(terminal.backend().buffer(), "Entered Peru").is_some());
+    assert!(find(terminal.backend().buffer(), "Entered Brazil").is_none());
+
+    let mut events = ScriptedEvents::new([press(KeyCode::Up)]);
+    let _ = run_app(&mut terminal, &mut app, &mut iss, &mut events, chrono::Local::now());
+    assert_eq!(app.events_scroll, 1);
+}